@@ -18,14 +18,18 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use crate::data::NoogleResponse;
+use crate::sources::{self, DocSource};
 
 const API_URL: &str = "https://noogle.dev/api/v1/data";
 const CACHE_DIR_NAME: &str = "noogle-search";
 const DATA_FILE: &str = "data.json";
 const METADATA_FILE: &str = "metadata.json";
+const MERGED_FILE: &str = "merged.json";
+const MERGED_STAMP_FILE: &str = "merged_stamp.json";
 const DISCLAIMER_FLAG: &str = ".disclaimer_shown";
 const TTL_HOURS: i64 = 24;
 
@@ -33,55 +37,238 @@ const TTL_HOURS: i64 = 24;
 struct Metadata {
     last_fetched: DateTime<Utc>,
     data_version: String,
+    /// Built by `index` from a local checkout; kept until the next refresh.
+    #[serde(default)]
+    local: bool,
 }
 
-pub fn load_data() -> Result<NoogleResponse> {
-    let cache_dir = get_cache_dir()?;
-    fs::create_dir_all(&cache_dir)?;
+/// What the merged dataset was built from; it is rebuilt once any of this changes.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct MergedStamp {
+    sources: Vec<DocSource>,
+    /// Modification times of the data and of each source, in that order.
+    modified: Vec<Option<SystemTime>>,
+}
 
-    let data_path = cache_dir.join(DATA_FILE);
-    let metadata_path = cache_dir.join(METADATA_FILE);
+/// Fetches the Noogle dataset and caches it on disk between runs.
+#[derive(Debug, Clone)]
+pub struct NoogleClient {
+    cache_dir: PathBuf,
+    api_url: String,
+    ttl: Duration,
+}
 
-    let needs_fetch = if metadata_path.exists() {
-        let metadata_content = fs::read_to_string(&metadata_path)?;
-        let metadata: Metadata = serde_json::from_str(&metadata_content)?;
+impl NoogleClient {
+    /// A client using `~/.cache/noogle-search` and the public Noogle API.
+    pub fn new() -> Result<Self> {
+        Ok(NoogleClient {
+            cache_dir: get_cache_dir()?,
+            api_url: API_URL.to_string(),
+            ttl: Duration::hours(TTL_HOURS),
+        })
+    }
 
-        let age = Utc::now() - metadata.last_fetched;
-        age > Duration::hours(TTL_HOURS)
-    } else {
-        true
-    };
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
 
-    if needs_fetch || !data_path.exists() {
-        fetch_and_cache(&data_path, &metadata_path)?;
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
     }
 
-    let data_content = fs::read_to_string(&data_path).context("Failed to read cached data")?;
+    /// How long cached data is used before it is fetched again.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
 
-    let response: NoogleResponse =
-        serde_json::from_str(&data_content).context("Failed to parse cached data")?;
+    /// Returns the cached data, fetching it first if it is missing or stale.
+    pub fn load(&self) -> Result<NoogleResponse> {
+        let data_path = self.update()?;
+        read_data(&data_path)
+    }
 
-    Ok(response)
-}
+    /// Like [`load`], with the docs of `sources` merged in.
+    ///
+    /// The merged dataset is cached too and reused until the data or one of the sources
+    /// changes, so repeated runs don't parse every source again.
+    ///
+    /// [`load`]: NoogleClient::load
+    pub fn load_merged(&self, sources: &[DocSource]) -> Result<NoogleResponse> {
+        let data_path = self.update()?;
+        if sources.is_empty() {
+            return read_data(&data_path);
+        }
 
-fn fetch_and_cache(data_path: &PathBuf, metadata_path: &PathBuf) -> Result<()> {
-    let response = reqwest::blocking::get(API_URL).context("Failed to fetch from Noogle API")?;
+        let stamp = MergedStamp {
+            sources: sources.to_vec(),
+            modified: std::iter::once(data_path.as_path())
+                .chain(sources.iter().map(|source| source.path.as_path()))
+                .map(modified)
+                .collect(),
+        };
+        let merged_path = self.cache_dir.join(MERGED_FILE);
+        let stamp_path = self.cache_dir.join(MERGED_STAMP_FILE);
 
-    let body = response.text().context("Failed to read API response")?;
+        let cached_stamp = fs::read_to_string(&stamp_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<MergedStamp>(&content).ok());
+        if cached_stamp.as_ref() == Some(&stamp)
+            && let Ok(content) = fs::read_to_string(&merged_path)
+            && let Ok(response) = serde_json::from_str(&content)
+        {
+            return Ok(response);
+        }
 
-    fs::write(data_path, &body).context("Failed to write data cache")?;
+        let mut response = read_data(&data_path)?;
+        sources::merge(&mut response, sources)?;
 
-    let response_data: NoogleResponse = serde_json::from_str(&body)?;
+        fs::write(&merged_path, serde_json::to_string(&response)?)
+            .context("Failed to write merged data cache")?;
+        fs::write(&stamp_path, serde_json::to_string(&stamp)?)
+            .context("Failed to write merged data cache")?;
 
-    let metadata = Metadata {
-        last_fetched: Utc::now(),
-        data_version: response_data.upstream_info.rev.clone(),
-    };
+        Ok(response)
+    }
 
-    let metadata_json = serde_json::to_string_pretty(&metadata)?;
-    fs::write(metadata_path, metadata_json).context("Failed to write metadata")?;
+    /// Fetches the data if it is missing or stale, returning the path of the cached copy.
+    fn update(&self) -> Result<PathBuf> {
+        fs::create_dir_all(&self.cache_dir)?;
 
-    Ok(())
+        let data_path = self.cache_dir.join(DATA_FILE);
+        let metadata_path = self.cache_dir.join(METADATA_FILE);
+
+        let needs_fetch = if metadata_path.exists() {
+            let metadata_content = fs::read_to_string(&metadata_path)?;
+            let metadata: Metadata = serde_json::from_str(&metadata_content)?;
+
+            let age = Utc::now() - metadata.last_fetched;
+            !metadata.local && age > self.ttl
+        } else {
+            true
+        };
+
+        if needs_fetch || !data_path.exists() {
+            self.fetch_and_cache(&data_path, &metadata_path)?;
+        }
+
+        Ok(data_path)
+    }
+
+    /// The cached data as it is, without fetching; `None` if there is none.
+    pub fn cached(&self) -> Option<NoogleResponse> {
+        let data_content = fs::read_to_string(self.cache_dir.join(DATA_FILE)).ok()?;
+        serde_json::from_str(&data_content).ok()
+    }
+
+    /// Fetches fresh data regardless of the cache age.
+    pub fn refresh(&self) -> Result<NoogleResponse> {
+        fs::create_dir_all(&self.cache_dir)?;
+        self.fetch_and_cache(
+            &self.cache_dir.join(DATA_FILE),
+            &self.cache_dir.join(METADATA_FILE),
+        )?;
+        self.load()
+    }
+
+    /// Caches a locally built dataset in place of the Noogle data until the next [`refresh`].
+    ///
+    /// [`refresh`]: NoogleClient::refresh
+    pub fn store(&self, response: &NoogleResponse) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir)?;
+
+        let body = serde_json::to_string(response)?;
+        fs::write(self.cache_dir.join(DATA_FILE), body).context("Failed to write data cache")?;
+
+        let metadata = Metadata {
+            last_fetched: Utc::now(),
+            data_version: response.upstream_info.rev.clone(),
+            local: true,
+        };
+
+        let metadata_json = serde_json::to_string_pretty(&metadata)?;
+        fs::write(self.cache_dir.join(METADATA_FILE), metadata_json)
+            .context("Failed to write metadata")?;
+
+        Ok(())
+    }
+
+    fn fetch_and_cache(&self, data_path: &Path, metadata_path: &Path) -> Result<()> {
+        let response =
+            reqwest::blocking::get(&self.api_url).context("Failed to fetch from Noogle API")?;
+
+        let body = response.text().context("Failed to read API response")?;
+
+        fs::write(data_path, &body).context("Failed to write data cache")?;
+
+        let response_data: NoogleResponse = serde_json::from_str(&body)?;
+
+        let metadata = Metadata {
+            last_fetched: Utc::now(),
+            data_version: response_data.upstream_info.rev.clone(),
+            local: false,
+        };
+
+        let metadata_json = serde_json::to_string_pretty(&metadata)?;
+        fs::write(metadata_path, metadata_json).context("Failed to write metadata")?;
+
+        Ok(())
+    }
+}
+
+fn read_data(data_path: &Path) -> Result<NoogleResponse> {
+    let data_content = fs::read_to_string(data_path).context("Failed to read cached data")?;
+
+    let response: NoogleResponse =
+        serde_json::from_str(&data_content).context("Failed to parse cached data")?;
+
+    Ok(response)
+}
+
+/// When `path` last changed; for a directory, the latest change of it, its
+/// subdirectories and the `*.nix` files in them.
+///
+/// `path` itself may be a symlink, but links below it aren't followed and hidden
+/// entries such as `.git` are skipped, so a checkout is walked once and only as deep as
+/// its Nix sources.
+fn modified(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    let mut latest = metadata.modified().ok()?;
+    if metadata.is_dir() {
+        latest = latest.max(nix_modified(path).unwrap_or(latest));
+    }
+    Some(latest)
+}
+
+fn nix_modified(dir: &Path) -> Option<SystemTime> {
+    let mut latest = None;
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(metadata) = fs::symlink_metadata(entry.path()) else {
+            continue;
+        };
+        let time = if metadata.is_dir() {
+            let own = metadata.modified().ok();
+            own.max(nix_modified(&entry.path()))
+        } else if metadata.is_file() && name.ends_with(".nix") {
+            metadata.modified().ok()
+        } else {
+            None
+        };
+        latest = latest.max(time);
+    }
+    latest
+}
+
+/// Loads the data through a default [`NoogleClient`].
+pub fn load_data() -> Result<NoogleResponse> {
+    NoogleClient::new()?.load()
 }
 
 fn get_cache_dir() -> Result<PathBuf> {
@@ -104,3 +291,41 @@ pub fn mark_disclaimer_shown() -> Result<()> {
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    #[test]
+    fn only_nix_files_below_a_directory_count() {
+        let dir =
+            std::env::temp_dir().join(format!("noogle-search-modified-{}", std::process::id()));
+        let outside = dir.with_extension("outside");
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(dir.join("lib").join("strings.nix"), "{ }").unwrap();
+        fs::write(dir.join("README.md"), "").unwrap();
+        fs::write(outside.join("new.nix"), "{ }").unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("linked")).unwrap();
+
+        let base = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let touch = |path: &Path, secs| {
+            File::open(path)
+                .unwrap()
+                .set_modified(base + Duration::from_secs(secs))
+                .unwrap();
+        };
+        touch(&dir.join("lib").join("strings.nix"), 20);
+        touch(&dir.join("README.md"), 30);
+        touch(&outside.join("new.nix"), 40);
+        touch(&dir.join("lib"), 10);
+        touch(&dir, 10);
+
+        let time = modified(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+
+        assert_eq!(time, Some(base + Duration::from_secs(20)));
+    }
+}
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Context, Result, bail};
+use noogle_search::data::NoogleResponse;
+use noogle_search::examples::{self, Example};
+use std::process::Command;
+
+pub const DEFAULT_EVAL_COMMAND: &[&str] = &["nix-instantiate", "--eval", "--strict", "-E"];
+
+enum Outcome {
+    Pass,
+    Mismatch(String),
+    Error(String),
+}
+
+/// Evaluates every example with an expected result below `namespace` and reports mismatches.
+pub fn execute(
+    response: &NoogleResponse,
+    namespace: Option<&str>,
+    command: &[String],
+) -> Result<()> {
+    let (program, args) = command.split_first().context("The eval command is empty")?;
+    let prefix = namespace.map(|ns| format!("{}.", ns));
+
+    let (mut passed, mut failed, mut errors, mut skipped) = (0, 0, 0, 0);
+
+    for doc in &response.data {
+        if prefix
+            .as_ref()
+            .is_some_and(|prefix| !doc.meta.title.starts_with(prefix.as_str()))
+        {
+            continue;
+        }
+
+        for example in examples::extract(doc) {
+            let Some(expression) = examples::check_expression(doc, &example) else {
+                skipped += 1;
+                continue;
+            };
+
+            match evaluate(program, args, &expression)? {
+                Outcome::Pass => passed += 1,
+                Outcome::Mismatch(actual) => {
+                    failed += 1;
+                    report("FAIL", &example);
+                    println!("  actual:     {}", actual);
+                }
+                Outcome::Error(message) => {
+                    errors += 1;
+                    report("ERROR", &example);
+                    println!("  error:      {}", message);
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} errors, {} without an expected result",
+        passed, failed, errors, skipped
+    );
+
+    if failed + errors > 0 {
+        bail!("{} examples did not match", failed + errors);
+    }
+
+    Ok(())
+}
+
+fn evaluate(program: &str, args: &[String], expression: &str) -> Result<Outcome> {
+    let output = Command::new(program)
+        .args(args)
+        .arg(expression)
+        .output()
+        .with_context(|| format!("Failed to run {}", program))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or("evaluation failed")
+            .trim()
+            .to_string();
+        return Ok(Outcome::Error(message));
+    }
+
+    if stdout == examples::PASS {
+        Ok(Outcome::Pass)
+    } else {
+        Ok(Outcome::Mismatch(stdout))
+    }
+}
+
+fn report(status: &str, example: &Example) {
+    println!("{} {} #{}", status, example.function, example.index);
+    println!("  expression: {}", one_line(&example.expression));
+    if let Some(expected) = &example.expected {
+        println!("  expected:   {}", one_line(expected));
+    }
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// An eval command running `script` through `sh`, with the expression as `$1`.
+    fn stub(script: &str) -> Vec<String> {
+        ["sh", "-c", script, "sh"].map(str::to_string).to_vec()
+    }
+
+    fn response() -> NoogleResponse {
+        serde_json::from_value(json!({
+            "data": [{
+                "meta": { "title": "lib.trivial.add", "path": ["lib", "trivial", "add"], "is_primop": false },
+                "content": { "content": "Adds.\n\n# Examples\n\n```nix\nadd 1 2\n=> 3\n```\n" },
+            }],
+            "builtinTypes": {},
+            "upstreamInfo": { "rev": "abc", "lastModified": 0 },
+            "nixInfo": { "rev": "abc", "lastModified": 0 },
+        }))
+        .unwrap()
+    }
+
+    fn run(script: &str, expression: &str) -> Outcome {
+        let command = stub(script);
+        evaluate(&command[0], &command[1..], expression).unwrap()
+    }
+
+    #[test]
+    fn pass_when_the_evaluator_prints_the_marker() {
+        let outcome = run(&format!("printf '%s\\n' '{}'", examples::PASS), "1");
+        assert!(matches!(outcome, Outcome::Pass));
+    }
+
+    #[test]
+    fn mismatch_reports_the_actual_value() {
+        let outcome = run("echo 42", "1");
+        assert!(matches!(outcome, Outcome::Mismatch(actual) if actual == "42"));
+    }
+
+    #[test]
+    fn error_reports_the_first_stderr_line() {
+        let outcome = run("echo; echo 'error: boom' >&2; exit 1", "1");
+        assert!(matches!(outcome, Outcome::Error(message) if message == "error: boom"));
+    }
+
+    #[test]
+    fn expression_is_passed_as_the_last_argument() {
+        let outcome = run(
+            "printf '%s' \"$1\" | grep -q 'add 1 2' && echo ok",
+            "add 1 2",
+        );
+        assert!(matches!(outcome, Outcome::Mismatch(actual) if actual == "ok"));
+    }
+
+    #[test]
+    fn execute_succeeds_when_every_example_passes() {
+        let command = stub(&format!("printf '%s\\n' '{}'", examples::PASS));
+        assert!(execute(&response(), None, &command).is_ok());
+    }
+
+    #[test]
+    fn execute_fails_on_mismatches_and_errors() {
+        assert!(execute(&response(), None, &stub("echo 4")).is_err());
+        assert!(execute(&response(), None, &stub("exit 1")).is_err());
+    }
+
+    #[test]
+    fn execute_skips_other_namespaces() {
+        assert!(execute(&response(), Some("builtins"), &stub("exit 1")).is_ok());
+    }
+}
@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Result, bail};
+use clap::{Command, ValueEnum};
+use clap_complete::{Shell, generate};
+use clap_complete_nushell::Nushell;
+use noogle_search::data::NoogleResponse;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+const BIN: &str = "noogle-search";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Nushell,
+}
+
+/// Values offered by the dynamic part of the completion scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionValues {
+    /// Every function name and alias
+    Names,
+    /// Every namespace, for --filter
+    Namespaces,
+}
+
+/// Prints the completion script for `shell`.
+///
+/// clap generates the subcommands and flags; function names and namespaces are
+/// completed by calling back into `complete-values`, so they follow the cache.
+pub fn execute(command: Command, shell: CompletionShell) -> Result<()> {
+    io::stdout().write_all(script(command, shell)?.as_bytes())?;
+    Ok(())
+}
+
+fn script(mut command: Command, shell: CompletionShell) -> Result<String> {
+    let subcommands = name_subcommands(&command);
+    let mut script = Vec::new();
+    match shell {
+        CompletionShell::Bash => generate(Shell::Bash, &mut command, BIN, &mut script),
+        CompletionShell::Zsh => generate(Shell::Zsh, &mut command, BIN, &mut script),
+        CompletionShell::Fish => generate(Shell::Fish, &mut command, BIN, &mut script),
+        CompletionShell::Nushell => generate(Nushell, &mut command, BIN, &mut script),
+    }
+    let script = String::from_utf8(script)?;
+
+    match shell {
+        CompletionShell::Bash => bash(script, &subcommands),
+        CompletionShell::Zsh => zsh(script),
+        CompletionShell::Fish => fish(script, &subcommands),
+        CompletionShell::Nushell => nushell(script),
+    }
+}
+
+/// Replaces every `from` in clap's script, failing if there is none, so a change
+/// in clap_complete's output can't silently drop the dynamic completions.
+fn patch(script: &str, from: &str, to: &str) -> Result<String> {
+    if !script.contains(from) {
+        bail!(
+            "Unexpected completion script from clap_complete: `{}` not found",
+            from.trim()
+        );
+    }
+    Ok(script.replace(from, to))
+}
+
+/// Visible subcommands taking a function name as their `name` argument, with
+/// how many positionals come before it.
+fn name_subcommands(command: &Command) -> Vec<(String, usize)> {
+    command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .filter_map(|sub| {
+            let position = sub
+                .get_positionals()
+                .position(|arg| arg.get_id() == "name")?;
+            Some((sub.get_name().to_string(), position))
+        })
+        .collect()
+}
+
+/// Groups subcommands by the position of their `name` argument.
+fn by_position(subcommands: &[(String, usize)]) -> BTreeMap<usize, Vec<&str>> {
+    let mut groups: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for (name, position) in subcommands {
+        groups.entry(*position).or_default().push(name);
+    }
+    groups
+}
+
+/// Wraps clap's completion function, answering for names and `--filter` first.
+fn bash(script: String, subcommands: &[(String, usize)]) -> Result<String> {
+    // clap_complete mangles the `-` in our binary name differently in its case
+    // arms than in the `cmd` values it matches them against.
+    let script = patch(&script, "noogle__subcmd__search__", "noogle__search__")?;
+    if !script.contains("_noogle__search() {") {
+        bail!("Unexpected completion script from clap_complete: `_noogle__search` not found");
+    }
+    let arms: String = by_position(subcommands)
+        .into_iter()
+        .map(|(position, names)| {
+            format!(
+                "            {})\n                [[ ${{COMP_CWORD}} -ge {} ]] && names=1\n                ;;\n",
+                names.join("|"),
+                position + 2
+            )
+        })
+        .collect();
+    Ok(format!(
+        r#"{script}
+_noogle__search_dynamic() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    if [[ ${{prev}} == --filter || ${{prev}} == -f ]]; then
+        COMPREPLY=( $(compgen -W "$({BIN} complete-values namespaces 2>/dev/null)" -- "${{cur}}") )
+        return 0
+    fi
+    local names=0
+    if [[ ${{cur}} != -* && ${{prev}} != -* ]]; then
+        case "${{COMP_WORDS[1]}}" in
+{arms}        esac
+    fi
+    if [[ ${{names}} == 1 ]]; then
+        COMPREPLY=( $(compgen -W "$({BIN} complete-values names 2>/dev/null)" -- "${{cur}}") )
+        return 0
+    fi
+    _noogle__search "$@"
+}}
+
+complete -F _noogle__search_dynamic -o bashdefault -o default {BIN}
+"#
+    ))
+}
+
+fn zsh(script: String) -> Result<String> {
+    let script = patch(&script, ":name:_default'", ":name:_noogle-search_names'")?;
+    let script = patch(
+        &script,
+        ":FILTER:_default'",
+        ":FILTER:_noogle-search_namespaces'",
+    )?;
+    let functions = format!(
+        r#"
+(( $+functions[_noogle-search_names] )) ||
+_noogle-search_names() {{
+    local names; names=(${{(f)"$({BIN} complete-values names 2>/dev/null)"}})
+    compadd -a names
+}}
+(( $+functions[_noogle-search_namespaces] )) ||
+_noogle-search_namespaces() {{
+    local namespaces; namespaces=(${{(f)"$({BIN} complete-values namespaces 2>/dev/null)"}})
+    compadd -a namespaces
+}}
+"#
+    );
+
+    // The functions must exist before the script's final compdef/dispatch runs.
+    match script.rfind("\nif [ \"$funcstack[1]\"") {
+        Some(at) => Ok(format!("{}{}{}", &script[..at], functions, &script[at..])),
+        None => bail!("Unexpected completion script from clap_complete: no compdef dispatch"),
+    }
+}
+
+fn fish(script: String, subcommands: &[(String, usize)]) -> Result<String> {
+    let mut script = patch(
+        &script,
+        " -l filter -r",
+        &format!(
+            " -l filter -r -f -a \"({} complete-values namespaces)\"",
+            BIN
+        ),
+    )?;
+    for (position, names) in by_position(subcommands) {
+        let mut condition = format!(
+            "__fish_{}_using_subcommand {}",
+            BIN.replace('-', "_"),
+            names.join(" ")
+        );
+        // Earlier positionals, like `url`'s kind, keep clap's completions.
+        for earlier in 2..position + 2 {
+            let _ = write!(condition, "; and not __fish_is_nth_token {}", earlier);
+        }
+        let _ = writeln!(
+            script,
+            "complete -c {} -n \"{}\" -f -a \"({} complete-values names)\"",
+            BIN, condition, BIN
+        );
+    }
+    Ok(script)
+}
+
+fn nushell(script: String) -> Result<String> {
+    let script = patch(
+        &script,
+        "    name: string\n",
+        &format!("    name: string@\"nu-complete {} names\"\n", BIN),
+    )?;
+    let script = patch(
+        &script,
+        "--filter(-f): string\n",
+        &format!("--filter(-f): string@\"nu-complete {} namespaces\"\n", BIN),
+    )?;
+    // Subcommands with a long-only `--filter`, if any, are patched too.
+    let script = script.replace(
+        "--filter: string\n",
+        &format!("--filter: string@\"nu-complete {} namespaces\"\n", BIN),
+    );
+    let functions = format!(
+        r#"module completions {{
+
+  def "nu-complete {BIN} names" [] {{
+    ^{BIN} complete-values names | lines
+  }}
+
+  def "nu-complete {BIN} namespaces" [] {{
+    ^{BIN} complete-values namespaces | lines
+  }}
+"#
+    );
+    if !script.contains("module completions {\n") {
+        bail!("Unexpected completion script from clap_complete: no completions module");
+    }
+    Ok(script.replacen("module completions {\n", &functions, 1))
+}
+
+/// Lists completion candidates from the cache, one per line.
+pub fn values(response: &NoogleResponse, kind: CompletionValues) {
+    match kind {
+        CompletionValues::Names => {
+            for doc in &response.data {
+                for name in doc.all_names() {
+                    println!("{}", name);
+                }
+            }
+        }
+        CompletionValues::Namespaces => {
+            for namespace in response.namespaces() {
+                println!("{}", namespace);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cli;
+    use clap::CommandFactory;
+
+    fn generated(shell: CompletionShell) -> String {
+        script(Cli::command(), shell).unwrap()
+    }
+
+    #[test]
+    fn bash_completes_names_and_namespaces() {
+        let script = generated(CompletionShell::Bash);
+        assert!(script.contains("complete-values names"));
+        assert!(script.contains("complete-values namespaces"));
+        assert!(script.contains("complete -F _noogle__search_dynamic"));
+        assert!(!script.contains("noogle__subcmd__search__"));
+        assert!(script.contains("            preview|"));
+    }
+
+    #[test]
+    fn zsh_completes_names_and_namespaces() {
+        let script = generated(CompletionShell::Zsh);
+        assert!(script.contains(":name:_noogle-search_names'"));
+        assert!(script.contains(":FILTER:_noogle-search_namespaces'"));
+        let functions = script.find("_noogle-search_names() {").unwrap();
+        assert!(functions < script.rfind("\nif [ \"$funcstack[1]\"").unwrap());
+    }
+
+    #[test]
+    fn fish_completes_names_and_namespaces() {
+        let script = generated(CompletionShell::Fish);
+        assert!(
+            script.contains("-l filter -r -f -a \"(noogle-search complete-values namespaces)\"")
+        );
+        assert!(script.contains("-f -a \"(noogle-search complete-values names)\""));
+    }
+
+    #[test]
+    fn nushell_completes_names_and_namespaces() {
+        let script = generated(CompletionShell::Nushell);
+        assert!(script.contains("name: string@\"nu-complete noogle-search names\""));
+        assert!(script.contains("string@\"nu-complete noogle-search namespaces\""));
+        assert!(script.contains("def \"nu-complete noogle-search names\" []"));
+    }
+
+    #[test]
+    fn missing_patterns_are_errors() {
+        assert!(patch("abc", "x", "y").is_err());
+        assert_eq!(patch("axa", "x", "y").unwrap(), "aya");
+        assert!(nushell("module other {\n".to_string()).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::url::{UrlKind, url_for};
+use anyhow::Result;
+use clap::ValueEnum;
+use noogle_search::data::{Doc, NoogleResponse};
+use noogle_search::index::Index;
+use std::env;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CopyTarget {
+    /// The function's full name
+    Name,
+    /// The source URL
+    Source,
+    /// The noogle.dev URL
+    Noogle,
+    /// A call with one placeholder per argument
+    Usage,
+}
+
+/// Copies text about a function to the clipboard with an OSC 52 escape sequence.
+pub fn execute(response: &NoogleResponse, target: CopyTarget, input: &str) -> Result<()> {
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, input)?;
+
+    let text = match target {
+        CopyTarget::Name => doc.meta.title.clone(),
+        CopyTarget::Source => url_for(response, doc, UrlKind::Source)?,
+        CopyTarget::Noogle => url_for(response, doc, UrlKind::Noogle)?,
+        CopyTarget::Usage => usage_snippet(doc),
+    };
+
+    osc52(&text)?;
+    eprintln!("Copied: {}", text);
+
+    Ok(())
+}
+
+/// A call expression with one placeholder per argument, e.g. `builtins.split regex str`.
+pub fn usage_snippet(doc: &Doc) -> String {
+    let args = match &doc.meta.primop_meta {
+        Some(primop) if !primop.args.is_empty() => primop.args.clone(),
+        _ => signature_args(doc.meta.signature.as_deref().unwrap_or_default()),
+    };
+
+    std::iter::once(doc.meta.title.clone())
+        .chain(args)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Placeholders for the argument types of a `name :: a -> b -> c` signature.
+fn signature_args(signature: &str) -> Vec<String> {
+    let ty = signature
+        .split_once("::")
+        .filter(|(name, _)| {
+            let name = name.trim();
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "_'-.".contains(c))
+        })
+        .map_or(signature, |(_, ty)| ty);
+    let mut args = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+
+    let bytes = ty.as_bytes();
+    for (i, c) in ty.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '-' if depth == 0 && bytes.get(i + 1) == Some(&b'>') => {
+                let arg = ty[start..i].trim();
+                let arg = arg
+                    .strip_prefix('(')
+                    .and_then(|a| a.strip_suffix(')'))
+                    .unwrap_or(arg);
+                args.push(format!("<{}>", arg));
+                start = i + 2;
+            }
+            _ => {}
+        }
+    }
+
+    args
+}
+
+/// Writes an OSC 52 sequence to the terminal, wrapped for tmux when running inside it.
+fn osc52(text: &str) -> Result<()> {
+    let mut sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if env::var_os("TMUX").is_some() {
+        sequence = format!("\x1bPtmux;\x1b{}\x1b\\", sequence);
+    }
+
+    match OpenOptions::new().write(true).open("/dev/tty") {
+        Ok(mut tty) => tty.write_all(sequence.as_bytes())?,
+        Err(_) => io::stdout().write_all(sequence.as_bytes())?,
+    }
+
+    Ok(())
+}
+
+fn base64(input: &[u8]) -> String {
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn signature_args_split_top_level_arrows() {
+        assert_eq!(
+            signature_args("splitString :: string -> string -> [string]"),
+            ["<string>", "<string>"]
+        );
+        assert_eq!(
+            signature_args("mapAttrs :: (String -> Any -> Any) -> AttrSet -> AttrSet"),
+            ["<String -> Any -> Any>", "<AttrSet>"]
+        );
+        assert_eq!(
+            signature_args("{ a :: Int -> Int } -> Int"),
+            ["<{ a :: Int -> Int }>"]
+        );
+        assert!(signature_args("id").is_empty());
+    }
+
+    #[test]
+    fn usage_snippets_prefer_primop_argument_names() {
+        let doc = |meta| serde_json::from_value::<Doc>(json!({ "meta": meta })).unwrap();
+
+        let primop = doc(json!({
+            "title": "builtins.split",
+            "path": ["builtins", "split"],
+            "is_primop": true,
+            "signature": "split :: String -> String -> [String]",
+            "primop_meta": { "args": ["regex", "str"], "arity": 2 },
+        }));
+        assert_eq!(usage_snippet(&primop), "builtins.split regex str");
+
+        let lambda = doc(json!({
+            "title": "lib.strings.splitString",
+            "path": ["lib", "strings", "splitString"],
+            "is_primop": false,
+            "signature": "splitString :: string -> string -> [string]",
+        }));
+        assert_eq!(
+            usage_snippet(&lambda),
+            "lib.strings.splitString <string> <string>"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use noogle_search::data::{Doc, NoogleResponse};
+use noogle_search::{examples, urls};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum CoverageFormat {
+    /// A summary table plus the undocumented functions
+    #[default]
+    Markdown,
+    /// The same data as JSON, for comparing snapshots
+    Json,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Counts {
+    functions: usize,
+    content: usize,
+    signature: usize,
+    examples: usize,
+    position: usize,
+}
+
+impl Counts {
+    fn add(&mut self, doc: &Doc) {
+        self.functions += 1;
+        self.content += has_content(doc) as usize;
+        self.signature += doc.meta.signature.is_some() as usize;
+        self.examples += !examples::extract(doc).is_empty() as usize;
+        self.position += urls::source_position(doc).is_some() as usize;
+    }
+}
+
+#[derive(Serialize)]
+struct NamespaceCoverage {
+    namespace: String,
+    #[serde(flatten)]
+    counts: Counts,
+    undocumented: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Report {
+    rev: String,
+    last_modified: u64,
+    total: Counts,
+    namespaces: Vec<NamespaceCoverage>,
+}
+
+/// Summarizes per namespace how many functions have docs, signatures, examples and positions.
+pub fn execute(
+    response: &NoogleResponse,
+    namespace: Option<&str>,
+    format: CoverageFormat,
+) -> Result<()> {
+    let report = report(response, namespace);
+    match format {
+        CoverageFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        CoverageFormat::Markdown => print!("{}", render_markdown(&report)),
+    }
+
+    Ok(())
+}
+
+fn report(response: &NoogleResponse, namespace: Option<&str>) -> Report {
+    let prefix = namespace.map(|ns| format!("{}.", ns));
+    let mut total = Counts::default();
+    let mut namespaces: BTreeMap<String, NamespaceCoverage> = BTreeMap::new();
+
+    for doc in &response.data {
+        if prefix
+            .as_ref()
+            .is_some_and(|prefix| !doc.meta.title.starts_with(prefix.as_str()))
+        {
+            continue;
+        }
+
+        let name = doc.namespace();
+        let entry = namespaces
+            .entry(name.clone())
+            .or_insert_with(|| NamespaceCoverage {
+                namespace: name,
+                counts: Counts::default(),
+                undocumented: Vec::new(),
+            });
+        entry.counts.add(doc);
+        total.add(doc);
+        if !has_content(doc) {
+            entry.undocumented.push(doc.meta.title.clone());
+        }
+    }
+
+    Report {
+        rev: response.upstream_info.rev.clone(),
+        last_modified: response.upstream_info.last_modified,
+        total,
+        namespaces: namespaces.into_values().collect(),
+    }
+}
+
+fn has_content(doc: &Doc) -> bool {
+    doc.content
+        .as_ref()
+        .and_then(|c| c.content.as_deref())
+        .is_some_and(|text| !text.trim().is_empty())
+}
+
+fn render_markdown(report: &Report) -> String {
+    let mut out = format!("# Documentation coverage\n\nnixpkgs `{}`\n\n", report.rev);
+
+    out.push_str("| Namespace | Functions | Docs | Signature | Examples | Position |\n");
+    out.push_str("| --- | ---: | ---: | ---: | ---: | ---: |\n");
+    for namespace in &report.namespaces {
+        out.push_str(&row(
+            &format!("`{}`", namespace.namespace),
+            &namespace.counts,
+        ));
+    }
+    out.push_str(&row("**Total**", &report.total));
+
+    let undocumented: Vec<_> = report
+        .namespaces
+        .iter()
+        .filter(|n| !n.undocumented.is_empty())
+        .collect();
+    if !undocumented.is_empty() {
+        out.push_str("\n## Undocumented\n");
+        for namespace in undocumented {
+            out.push_str(&format!("\n### `{}`\n\n", namespace.namespace));
+            for title in &namespace.undocumented {
+                out.push_str(&format!("- `{}`\n", title));
+            }
+        }
+    }
+
+    out
+}
+
+fn row(label: &str, counts: &Counts) -> String {
+    format!(
+        "| {} | {} | {} | {} | {} | {} |\n",
+        label,
+        counts.functions,
+        percent(counts.content, counts.functions),
+        percent(counts.signature, counts.functions),
+        percent(counts.examples, counts.functions),
+        percent(counts.position, counts.functions)
+    )
+}
+
+fn percent(part: usize, whole: usize) -> String {
+    if whole == 0 {
+        return "-".to_string();
+    }
+    format!("{} ({:.0}%)", part, part as f64 * 100.0 / whole as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn response() -> NoogleResponse {
+        let position =
+            json!({ "file": "/nix/store/abc-source/lib/strings.nix", "line": 1, "column": 1 });
+        serde_json::from_value(json!({
+            "data": [
+                {
+                    "meta": {
+                        "title": "lib.strings.splitString",
+                        "path": ["lib", "strings", "splitString"],
+                        "signature": "splitString :: string -> string -> [string]",
+                        "is_primop": false,
+                        "lambda_position": position,
+                    },
+                    "content": {
+                        "content": "Cut a string.\n\n# Examples\n\n```nix\nsplitString \".\" \"a.b\"\n```\n",
+                    },
+                },
+                {
+                    "meta": {
+                        "title": "lib.strings.undocumented",
+                        "path": ["lib", "strings", "undocumented"],
+                        "is_primop": false,
+                    },
+                    "content": { "content": "  " },
+                },
+                {
+                    "meta": { "title": "builtins.map", "path": ["builtins", "map"], "is_primop": true },
+                    "content": { "content": "Apply a function to every element of a list." },
+                },
+            ],
+            "builtinTypes": {},
+            "upstreamInfo": { "rev": "abc", "lastModified": 42 },
+            "nixInfo": { "rev": "def", "lastModified": 0 },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn counts_are_kept_per_namespace() {
+        let report = report(&response(), None);
+        let strings = &report.namespaces[1];
+        assert_eq!(strings.namespace, "lib.strings");
+        assert_eq!(
+            json!(strings.counts),
+            json!({ "functions": 2, "content": 1, "signature": 1, "examples": 1, "position": 1 })
+        );
+        assert_eq!(strings.undocumented, ["lib.strings.undocumented"]);
+        assert_eq!(report.namespaces[0].namespace, "builtins");
+        assert!(report.namespaces[0].undocumented.is_empty());
+        assert_eq!(report.total.functions, 3);
+        assert_eq!(report.total.content, 2);
+
+        let report = super::report(&response(), Some("lib"));
+        assert_eq!(report.namespaces.len(), 1);
+        assert_eq!(report.total.functions, 2);
+    }
+
+    #[test]
+    fn json_flattens_the_counts() {
+        let value: Value = serde_json::to_value(report(&response(), Some("builtins"))).unwrap();
+        assert_eq!(value["rev"], "abc");
+        assert_eq!(value["last_modified"], 42);
+        assert_eq!(value["total"]["functions"], 1);
+        assert_eq!(
+            value["namespaces"][0],
+            json!({
+                "namespace": "builtins",
+                "functions": 1,
+                "content": 1,
+                "signature": 0,
+                "examples": 0,
+                "position": 0,
+                "undocumented": [],
+            })
+        );
+    }
+
+    #[test]
+    fn markdown_has_a_table_and_the_undocumented_list() {
+        let markdown = render_markdown(&report(&response(), None));
+        assert!(markdown.starts_with("# Documentation coverage\n\nnixpkgs `abc`\n"));
+        assert!(
+            markdown.contains("| `lib.strings` | 2 | 1 (50%) | 1 (50%) | 1 (50%) | 1 (50%) |\n")
+        );
+        assert!(markdown.contains("| **Total** | 3 | 2 (67%) | 1 (33%) | 1 (33%) | 1 (33%) |\n"));
+        assert!(
+            markdown.ends_with(
+                "## Undocumented\n\n### `lib.strings`\n\n- `lib.strings.undocumented`\n"
+            )
+        );
+        assert!(!markdown.contains("### `builtins`"));
+    }
+}
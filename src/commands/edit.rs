@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Context, Result, anyhow, bail};
+use noogle_search::data::NoogleResponse;
+use noogle_search::index::Index;
+use noogle_search::{nixpkgs, urls};
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+pub fn execute(response: &NoogleResponse, input: &str, checkout: Option<&Path>) -> Result<()> {
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, input)?;
+
+    let position =
+        urls::source_position(doc).ok_or_else(|| anyhow!("No source position available"))?;
+    let checkout = nixpkgs::checkout(response, checkout);
+    let path = nixpkgs::resolve(position, checkout).ok_or_else(|| {
+        anyhow!(
+            "{} is not available locally; set `nixpkgs` in the config file or NIX_PATH",
+            urls::relative_path(&position.file)
+        )
+    })?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().context("$EDITOR is empty")?;
+
+    let file = path.display().to_string();
+    let status = Command::new(program)
+        .args(words)
+        .args(goto_args(program, &file, position.line, position.column))
+        .status()
+        .with_context(|| format!("Failed to launch {}", program))?;
+
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+
+    Ok(())
+}
+
+/// Arguments opening `file` at a line and column in the editor's own syntax.
+fn goto_args(program: &str, file: &str, line: u32, column: u32) -> Vec<String> {
+    let name = Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program);
+
+    match name {
+        // Plain `vi` is often nvi or busybox, which only take `+line`.
+        "vim" | "nvim" | "gvim" => vec![
+            format!("+call cursor({}, {})", line, column),
+            file.to_string(),
+        ],
+        "nano" => vec![format!("+{},{}", line, column), file.to_string()],
+        "emacs" | "emacsclient" | "kak" | "micro" => {
+            vec![format!("+{}:{}", line, column), file.to_string()]
+        }
+        "code" | "codium" | "code-insiders" => {
+            vec![
+                "--goto".to_string(),
+                format!("{}:{}:{}", file, line, column),
+            ]
+        }
+        "hx" | "helix" | "subl" | "zed" => vec![format!("{}:{}:{}", file, line, column)],
+        _ => vec![format!("+{}", line), file.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_vim_gets_a_column() {
+        assert_eq!(
+            goto_args("/usr/bin/nvim", "a.nix", 3, 7),
+            ["+call cursor(3, 7)", "a.nix"]
+        );
+        assert_eq!(goto_args("vi", "a.nix", 3, 7), ["+3", "a.nix"]);
+    }
+}
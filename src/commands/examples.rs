@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use noogle_search::data::NoogleResponse;
+use noogle_search::examples;
+use noogle_search::index::Index;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExampleFormat {
+    /// A list of { function, index, expression, expected } objects
+    #[default]
+    Json,
+    /// A lib.debug.runTests file for nix-instantiate --eval --strict
+    Nix,
+}
+
+pub fn execute(response: &NoogleResponse, input: &str, format: ExampleFormat) -> Result<()> {
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, input)?;
+    let extracted = examples::extract(doc);
+
+    match format {
+        ExampleFormat::Json => println!("{}", serde_json::to_string_pretty(&extracted)?),
+        ExampleFormat::Nix => print!("{}", examples::render_nix_tests(&[(doc, extracted)])),
+    }
+
+    Ok(())
+}
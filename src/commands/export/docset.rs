@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Context, Result, bail};
+use noogle_search::data::{self, Doc, NoogleResponse};
+use noogle_search::format::html;
+use noogle_search::urls::SourceUrls;
+use rusqlite::{Connection, params};
+use std::fs;
+use std::path::Path;
+
+const DOCSET_NAME: &str = "Noogle";
+const BUNDLE_ID: &str = "noogle";
+
+/// Writes `<out_dir>/Noogle.docset`, replacing an earlier export; anything else at that
+/// path is only replaced with `force`.
+pub fn execute(response: &NoogleResponse, out_dir: &Path, force: bool) -> Result<()> {
+    let docset = out_dir.join(format!("{}.docset", DOCSET_NAME));
+    let resources = docset.join("Contents").join("Resources");
+    let documents = resources.join("Documents");
+
+    if let Ok(metadata) = fs::symlink_metadata(&docset) {
+        let is_docset = metadata.is_dir() && docset.join("Contents").join("Info.plist").is_file();
+        if !is_docset && !force {
+            bail!(
+                "{} exists but is not a docset; pass --force to replace it",
+                docset.display()
+            );
+        }
+        if metadata.is_dir() {
+            fs::remove_dir_all(&docset)
+        } else {
+            fs::remove_file(&docset)
+        }
+        .with_context(|| format!("Failed to remove old {}", docset.display()))?;
+    }
+    fs::create_dir_all(documents.join("functions"))?;
+    fs::create_dir_all(documents.join("namespaces"))?;
+
+    let rev = &response.upstream_info.rev;
+    fs::write(docset.join("Contents").join("Info.plist"), info_plist(rev))?;
+    fs::write(docset.join("meta.json"), meta_json(rev)?)?;
+    let source_urls = SourceUrls::new(response);
+    fs::write(documents.join("style.css"), html::STYLESHEET)?;
+
+    let counts = response.namespace_counts();
+    let top_level = data::child_namespaces(&counts, "");
+    let index = html::render_namespace(DOCSET_NAME, &[], &top_level, "");
+    fs::write(
+        documents.join("index.html"),
+        html::page(DOCSET_NAME, &index, ""),
+    )?;
+
+    let mut conn = Connection::open(resources.join("docSet.dsidx"))
+        .context("Failed to create search index")?;
+    conn.execute_batch(
+        "CREATE TABLE searchIndex(id INTEGER PRIMARY KEY, name TEXT, type TEXT, path TEXT);
+         CREATE UNIQUE INDEX anchor ON searchIndex (name, type, path);",
+    )?;
+    let tx = conn.transaction()?;
+
+    let links = html::Links::new(response, "../");
+    for doc in &response.data {
+        let href = html::doc_href(&doc.meta.title);
+        let body = html::render_doc(doc, &source_urls, Some(&links));
+        fs::write(
+            documents.join(&href),
+            html::page(&doc.meta.title, &body, "../"),
+        )?;
+
+        for name in doc.all_names() {
+            tx.execute(
+                "INSERT OR IGNORE INTO searchIndex(name, type, path) VALUES (?1, ?2, ?3)",
+                params![name, entry_type(doc, &name), href],
+            )?;
+        }
+    }
+
+    for namespace in counts.keys() {
+        let href = html::namespace_href(namespace);
+        let docs = response.docs_in_namespace(namespace);
+        let children = data::child_namespaces(&counts, namespace);
+        let body = html::render_namespace(namespace, &docs, &children, "../");
+        fs::write(documents.join(&href), html::page(namespace, &body, "../"))?;
+
+        tx.execute(
+            "INSERT OR IGNORE INTO searchIndex(name, type, path) VALUES (?1, 'Namespace', ?2)",
+            params![namespace, href],
+        )?;
+    }
+
+    tx.commit()?;
+
+    eprintln!("Wrote {}", docset.display());
+
+    Ok(())
+}
+
+fn entry_type(doc: &Doc, name: &str) -> &'static str {
+    if doc.meta.is_primop || name.starts_with("builtins.") {
+        "Builtin"
+    } else {
+        "Function"
+    }
+}
+
+fn info_plist(rev: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>CFBundleIdentifier</key>
+	<string>{id}</string>
+	<key>CFBundleName</key>
+	<string>{name}</string>
+	<key>CFBundleShortVersionString</key>
+	<string>{short_rev}</string>
+	<key>CFBundleVersion</key>
+	<string>{rev}</string>
+	<key>DocSetPlatformFamily</key>
+	<string>{id}</string>
+	<key>dashIndexFilePath</key>
+	<string>index.html</string>
+	<key>isDashDocset</key>
+	<true/>
+	<key>isJavaScriptEnabled</key>
+	<false/>
+</dict>
+</plist>
+"#,
+        id = BUNDLE_ID,
+        name = DOCSET_NAME,
+        short_rev = rev.chars().take(12).collect::<String>(),
+        rev = html::escape(rev),
+    )
+}
+
+fn meta_json(rev: &str) -> Result<String> {
+    let meta = serde_json::json!({
+        "name": DOCSET_NAME,
+        "title": DOCSET_NAME,
+        "version": rev,
+    });
+    Ok(serde_json::to_string_pretty(&meta)?)
+}
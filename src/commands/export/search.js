@@ -0,0 +1,67 @@
+// Client-side search over window.NOOGLE_INDEX, loaded from search-index.js.
+(function () {
+  "use strict";
+
+  const input = document.getElementById("search");
+  const results = document.getElementById("results");
+  const index = window.NOOGLE_INDEX || [];
+  const limit = 50;
+
+  // Lower is better; null when the query is not a subsequence of the name.
+  function score(name, query) {
+    const lower = name.toLowerCase();
+    const direct = lower.indexOf(query);
+    if (direct !== -1) {
+      return direct === lower.length - query.length ? 0 : 1 + direct / lower.length;
+    }
+    let at = 0;
+    for (const c of query) {
+      at = lower.indexOf(c, at);
+      if (at === -1) {
+        return null;
+      }
+      at += 1;
+    }
+    return 3;
+  }
+
+  function render(query) {
+    results.replaceChildren();
+    if (!query) {
+      return;
+    }
+
+    const matches = [];
+    for (const entry of index) {
+      let best = null;
+      for (const name of [entry.n].concat(entry.a)) {
+        const s = score(name, query);
+        if (s !== null && (best === null || s < best)) {
+          best = s;
+        }
+      }
+      if (best !== null) {
+        matches.push([best, entry]);
+      }
+    }
+    matches.sort((a, b) => a[0] - b[0] || a[1].n.length - b[1].n.length);
+
+    for (const [, entry] of matches.slice(0, limit)) {
+      const item = document.createElement("li");
+      const link = document.createElement("a");
+      link.href = entry.h;
+      link.textContent = entry.n;
+      item.appendChild(link);
+      if (entry.s) {
+        const summary = document.createElement("span");
+        summary.className = "summary";
+        summary.textContent = " " + entry.s;
+        item.appendChild(summary);
+      }
+      results.appendChild(item);
+    }
+  }
+
+  input.addEventListener("input", () => render(input.value.trim().toLowerCase()));
+  render(input.value.trim().toLowerCase());
+})();
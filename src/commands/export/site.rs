@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Context, Result};
+use noogle_search::data::{self, NoogleResponse};
+use noogle_search::format::{self, html, markdown};
+use noogle_search::urls::SourceUrls;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const SEARCH_SCRIPT: &str = include_str!("search.js");
+
+#[derive(Serialize)]
+struct SearchEntry<'a> {
+    #[serde(rename = "n")]
+    name: &'a str,
+    #[serde(rename = "a")]
+    aliases: Vec<String>,
+    #[serde(rename = "s")]
+    summary: String,
+    #[serde(rename = "h")]
+    href: String,
+}
+
+pub fn execute(response: &NoogleResponse, out_dir: &Path) -> Result<()> {
+    fs::create_dir_all(out_dir.join("functions"))
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+    fs::create_dir_all(out_dir.join("namespaces"))?;
+
+    let source_urls = SourceUrls::new(response);
+    let links = html::Links::new(response, "../");
+    let mut search_index = Vec::new();
+
+    for doc in &response.data {
+        let href = html::doc_href(&doc.meta.title);
+        let body = nav("../") + &html::render_doc(doc, &source_urls, Some(&links));
+        fs::write(
+            out_dir.join(&href),
+            html::page(&doc.meta.title, &body, "../"),
+        )?;
+
+        search_index.push(SearchEntry {
+            name: &doc.meta.title,
+            aliases: doc.all_names().into_iter().skip(1).collect(),
+            summary: doc
+                .content
+                .as_ref()
+                .and_then(|c| c.content.as_deref())
+                .and_then(markdown::summary)
+                .map(|s| format::strip_inline_code(&s))
+                .unwrap_or_default(),
+            href,
+        });
+    }
+
+    let counts = response.namespace_counts();
+    for namespace in counts.keys() {
+        let docs = response.docs_in_namespace(namespace);
+        let children = data::child_namespaces(&counts, namespace);
+        let body = nav("../") + &html::render_namespace(namespace, &docs, &children, "../");
+        fs::write(
+            out_dir.join(html::namespace_href(namespace)),
+            html::page(namespace, &body, "../"),
+        )?;
+    }
+
+    fs::write(out_dir.join("style.css"), html::STYLESHEET)?;
+    fs::write(out_dir.join("search.js"), SEARCH_SCRIPT)?;
+    fs::write(
+        out_dir.join("search-index.js"),
+        format!(
+            "window.NOOGLE_INDEX = {};\n",
+            serde_json::to_string(&search_index)?
+        ),
+    )?;
+    fs::write(
+        out_dir.join("index.html"),
+        html::page("Noogle", &index_body(response, &counts), ""),
+    )?;
+
+    eprintln!(
+        "Wrote {} function pages to {}",
+        response.data.len(),
+        out_dir.display()
+    );
+
+    Ok(())
+}
+
+fn nav(root: &str) -> String {
+    format!(
+        "<nav><a href=\"{}index.html\">Noogle index</a></nav>\n",
+        root
+    )
+}
+
+fn index_body(response: &NoogleResponse, counts: &BTreeMap<String, usize>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<h1>Noogle</h1>\n<p class=\"source\">nixpkgs {}, {} functions</p>",
+        html::escape(&response.upstream_info.rev),
+        response.data.len()
+    );
+    out.push_str(
+        "<input id=\"search\" type=\"search\" placeholder=\"Search functions\" autofocus>\n\
+         <ul id=\"results\"></ul>\n",
+    );
+    out.push_str("<h2>Namespaces</h2>\n");
+    write_tree(counts, "", &mut out);
+    out.push_str(
+        "<script src=\"search-index.js\"></script>\n<script src=\"search.js\"></script>\n",
+    );
+
+    out
+}
+
+fn write_tree(counts: &BTreeMap<String, usize>, namespace: &str, out: &mut String) {
+    let children = data::child_namespaces(counts, namespace);
+    if children.is_empty() {
+        return;
+    }
+
+    out.push_str("<ul class=\"tree\">\n");
+    for child in children {
+        let _ = writeln!(
+            out,
+            "<li><a href=\"{}\">{}</a> <span class=\"count\">({})</span>",
+            html::escape(&html::namespace_href(&child)),
+            html::escape(&child),
+            counts.get(&child).copied().unwrap_or_default()
+        );
+        write_tree(counts, &child, out);
+        out.push_str("</li>\n");
+    }
+    out.push_str("</ul>\n");
+}
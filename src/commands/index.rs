@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use noogle_search::{NoogleClient, nixdoc};
+use std::path::Path;
+
+/// Replaces the cached data with an index of `nixpkgs`, or with fresh Noogle data when `None`.
+pub fn execute(nixpkgs: Option<&Path>) -> Result<()> {
+    let client = NoogleClient::new()?;
+
+    let response = match nixpkgs {
+        Some(root) => {
+            let response = nixdoc::index_nixpkgs(root, client.cached().as_ref())?;
+            client.store(&response)?;
+            response
+        }
+        None => client.refresh()?,
+    };
+
+    eprintln!(
+        "Indexed {} functions at {}",
+        response.data.len(),
+        response.upstream_info.rev
+    );
+
+    Ok(())
+}
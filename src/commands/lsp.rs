@@ -0,0 +1,479 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::jsonrpc;
+use anyhow::Result;
+use noogle_search::data::{Doc, NoogleResponse};
+use noogle_search::format::markdown;
+use noogle_search::index::{Child, Index};
+use noogle_search::nixpkgs;
+use noogle_search::urls::{self, SourceUrls};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const COMPLETION_KIND_FUNCTION: u32 = 3;
+const COMPLETION_KIND_MODULE: u32 = 9;
+const SYNC_KIND_FULL: u32 = 1;
+
+struct Server<'a> {
+    index: Index<'a>,
+    nixpkgs: Option<&'a Path>,
+    documents: HashMap<String, String>,
+    /// Whether positions count UTF-16 code units, LSP's default, rather than characters.
+    utf16: bool,
+}
+
+/// Speaks LSP over stdio until the client sends `exit`.
+pub fn execute(response: &NoogleResponse, nixpkgs: Option<&Path>) -> Result<()> {
+    let mut server = Server {
+        index: Index::new(response),
+        nixpkgs: nixpkgs::checkout(response, nixpkgs),
+        documents: HashMap::new(),
+        utf16: true,
+    };
+    let mut reader = io::stdin().lock();
+    let mut writer = io::stdout().lock();
+
+    while let Some(message) = jsonrpc::read_framed(&mut reader)? {
+        let message = match message {
+            Ok(message) => message,
+            Err(err) => {
+                jsonrpc::write_framed(&mut writer, &jsonrpc::parse_error(&err))?;
+                continue;
+            }
+        };
+        let Some(method) = message["method"].as_str() else {
+            continue;
+        };
+        if method == "exit" {
+            break;
+        }
+
+        let params = &message["params"];
+        match message.get("id") {
+            Some(id) => {
+                let reply = match server.request(method, params) {
+                    Some(result) => jsonrpc::response(id, result),
+                    None => jsonrpc::error(id, jsonrpc::METHOD_NOT_FOUND, "Method not found"),
+                };
+                jsonrpc::write_framed(&mut writer, &reply)?;
+            }
+            None => server.notify(method, params),
+        }
+    }
+
+    Ok(())
+}
+
+impl Server<'_> {
+    fn request(&mut self, method: &str, params: &Value) -> Option<Value> {
+        match method {
+            "initialize" => Some(self.initialize(params)),
+            "shutdown" => Some(Value::Null),
+            "textDocument/hover" => Some(self.hover(params)),
+            "textDocument/completion" => Some(self.completion(params)),
+            "textDocument/definition" => Some(self.definition(params)),
+            _ => None,
+        }
+    }
+
+    /// Counts positions in characters if the client offers `utf-32`, else in UTF-16 units.
+    fn initialize(&mut self, params: &Value) -> Value {
+        let encodings = params["capabilities"]["general"]["positionEncodings"].as_array();
+        self.utf16 = !encodings.is_some_and(|e| e.iter().any(|e| e == "utf-32"));
+
+        json!({
+            "capabilities": {
+                "positionEncoding": if self.utf16 { "utf-16" } else { "utf-32" },
+                "textDocumentSync": SYNC_KIND_FULL,
+                "hoverProvider": true,
+                "completionProvider": { "triggerCharacters": ["."] },
+                "definitionProvider": true,
+            },
+            "serverInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        })
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.to_string(), text.to_string());
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                if let Some(text) = changes.and_then(|c| c.last()?["text"].as_str()) {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+            }
+            _ => {}
+        }
+    }
+
+    fn hover(&self, params: &Value) -> Value {
+        let Some(word) = self.word_at(params) else {
+            return Value::Null;
+        };
+        let Some(doc) = self.resolve(&word.text) else {
+            return Value::Null;
+        };
+
+        let source_urls = SourceUrls::new(self.index.response());
+        json!({
+            "contents": { "kind": "markdown", "value": markdown::render_doc(doc, &source_urls) },
+            "range": range(
+                word.line,
+                self.encode(&word.chars, word.start),
+                self.encode(&word.chars, word.end),
+            ),
+        })
+    }
+
+    fn completion(&self, params: &Value) -> Value {
+        let Some((line, character)) = position(params) else {
+            return json!([]);
+        };
+        let Some(text) = self.line(params, line) else {
+            return json!([]);
+        };
+
+        let chars: Vec<char> = text.chars().collect();
+        let end = self.decode(&chars, character);
+        let start = (0..end)
+            .rev()
+            .take_while(|&i| is_ident(chars[i]))
+            .last()
+            .unwrap_or(end);
+        let prefix: String = chars[start..end].iter().collect();
+        let Some((namespace, partial)) = prefix.rsplit_once('.') else {
+            return json!([]);
+        };
+
+        let items: Vec<Value> = self
+            .index
+            .children(namespace)
+            .into_iter()
+            .filter_map(|child| {
+                let (name, kind, doc) = match child {
+                    Child::Namespace(name) => (name, COMPLETION_KIND_MODULE, None),
+                    Child::Doc { name, doc } => (name, COMPLETION_KIND_FUNCTION, Some(doc)),
+                };
+                let label = name.rsplit('.').next().unwrap_or(&name).to_string();
+                if !label.starts_with(partial) {
+                    return None;
+                }
+
+                let mut item = json!({ "label": label, "kind": kind });
+                if let Some(doc) = doc {
+                    item["detail"] =
+                        json!(doc.meta.signature.as_deref().unwrap_or(&doc.meta.title));
+                    if let Some(summary) = doc_summary(doc) {
+                        item["documentation"] = json!({ "kind": "markdown", "value": summary });
+                    }
+                }
+                Some(item)
+            })
+            .collect();
+
+        json!(items)
+    }
+
+    fn definition(&self, params: &Value) -> Value {
+        let location = self
+            .word_at(params)
+            .and_then(|word| self.resolve(&word.text))
+            .and_then(urls::source_position)
+            .and_then(|pos| Some((nixpkgs::resolve(pos, self.nixpkgs)?, pos)));
+
+        match location {
+            Some((path, pos)) => {
+                let path = fs::canonicalize(&path).unwrap_or(path);
+                let line = pos.line.saturating_sub(1) as usize;
+                // Nix counts columns in bytes.
+                let column = fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|source| {
+                        let text = source.lines().nth(line)?;
+                        let bytes = (pos.column.saturating_sub(1) as usize).min(text.len());
+                        let chars = text.get(..bytes)?.chars().count();
+                        Some(self.encode(&text.chars().collect::<Vec<_>>(), chars))
+                    })
+                    .unwrap_or(pos.column.saturating_sub(1) as usize);
+                json!({
+                    "uri": file_uri(&path),
+                    "range": range(line, column, column),
+                })
+            }
+            None => Value::Null,
+        }
+    }
+
+    /// The character index of an LSP `character` offset into `chars`.
+    fn decode(&self, chars: &[char], character: usize) -> usize {
+        if !self.utf16 {
+            return character.min(chars.len());
+        }
+        let mut units = 0;
+        for (i, c) in chars.iter().enumerate() {
+            if units >= character {
+                return i;
+            }
+            units += c.len_utf16();
+        }
+        chars.len()
+    }
+
+    /// The LSP `character` offset of the character index `index` into `chars`.
+    fn encode(&self, chars: &[char], index: usize) -> usize {
+        if !self.utf16 {
+            return index;
+        }
+        chars[..index.min(chars.len())]
+            .iter()
+            .map(|c| c.len_utf16())
+            .sum()
+    }
+
+    /// Looks a name up as written, then as a member of `lib` or `builtins` for `with lib;` code.
+    fn resolve(&self, name: &str) -> Option<&Doc> {
+        self.index.resolve(name, "")
+    }
+
+    fn line(&self, params: &Value, line: usize) -> Option<&str> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        self.documents.get(uri)?.lines().nth(line)
+    }
+
+    /// The dotted identifier under the cursor.
+    fn word_at(&self, params: &Value) -> Option<Word> {
+        let (line, character) = position(params)?;
+        let chars: Vec<char> = self.line(params, line)?.chars().collect();
+
+        let cursor = self.decode(&chars, character);
+        let mut start = cursor;
+        while start > 0 && is_ident(chars[start - 1]) {
+            start -= 1;
+        }
+        let mut end = cursor;
+        while end < chars.len() && is_ident(chars[end]) {
+            end += 1;
+        }
+        while start < end && chars[start] == '.' {
+            start += 1;
+        }
+        while end > start && chars[end - 1] == '.' {
+            end -= 1;
+        }
+
+        (start < end).then(|| Word {
+            text: chars[start..end].iter().collect(),
+            chars,
+            line,
+            start,
+            end,
+        })
+    }
+}
+
+struct Word {
+    text: String,
+    /// The whole line, which `start` and `end` index into.
+    chars: Vec<char>,
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+fn position(params: &Value) -> Option<(usize, usize)> {
+    let position = &params["position"];
+    Some((
+        position["line"].as_u64()? as usize,
+        position["character"].as_u64()? as usize,
+    ))
+}
+
+fn range(line: usize, start: usize, end: usize) -> Value {
+    json!({
+        "start": { "line": line, "character": start },
+        "end": { "line": line, "character": end },
+    })
+}
+
+/// A `file://` URI for an absolute path, percent-encoding everything but unreserved characters.
+fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            let _ = write!(uri, "%{:02X}", byte);
+        }
+    }
+    uri
+}
+
+fn doc_summary(doc: &Doc) -> Option<String> {
+    markdown::summary(doc.content.as_ref()?.content.as_deref()?)
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '\'' | '-' | '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const URI: &str = "file:///test.nix";
+
+    fn response(file: &str) -> NoogleResponse {
+        serde_json::from_value(json!({
+            "data": [
+                {
+                    "meta": {
+                        "title": "lib.strings.splitString",
+                        "path": ["lib", "strings", "splitString"],
+                        "aliases": [["lib", "splitString"]],
+                        "signature": "splitString :: string -> string -> [string]",
+                        "is_primop": false,
+                        "lambda_position": { "file": file, "line": 2, "column": 5 },
+                    },
+                    "content": { "content": "Cut a string with a separator." },
+                },
+                {
+                    "meta": {
+                        "title": "lib.strings.concatStrings",
+                        "path": ["lib", "strings", "concatStrings"],
+                        "is_primop": false,
+                    },
+                    "content": { "content": "Concatenate strings." },
+                },
+                {
+                    "meta": { "title": "builtins.map", "path": ["builtins", "map"], "is_primop": true },
+                    "content": { "content": "Apply a function to every element of a list." },
+                },
+            ],
+            "builtinTypes": {},
+            "upstreamInfo": { "rev": "abc", "lastModified": 0 },
+            "nixInfo": { "rev": "abc", "lastModified": 0 },
+        }))
+        .unwrap()
+    }
+
+    fn server<'a>(response: &'a NoogleResponse, text: &str) -> Server<'a> {
+        Server {
+            index: Index::new(response),
+            nixpkgs: None,
+            documents: HashMap::from([(URI.to_string(), text.to_string())]),
+            utf16: true,
+        }
+    }
+
+    fn at(line: usize, character: usize) -> Value {
+        json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } })
+    }
+
+    #[test]
+    fn hover_shows_the_doc_under_the_cursor() {
+        let response = response("/nowhere.nix");
+        let server = server(
+            &response,
+            "x = lib.strings.splitString \",\" s;\ny = builtins.map f l;\n",
+        );
+
+        let hover = server.hover(&at(0, 10));
+        let value = hover["contents"]["value"].as_str().unwrap();
+        assert!(value.contains("Cut a string with a separator."));
+        assert_eq!(hover["range"], range(0, 4, 27));
+
+        let hover = server.hover(&at(1, 14));
+        assert!(
+            hover["contents"]["value"]
+                .as_str()
+                .unwrap()
+                .contains("every element")
+        );
+        assert_eq!(server.hover(&at(1, 1)), Value::Null);
+    }
+
+    #[test]
+    fn positions_count_utf16_units() {
+        let response = response("/nowhere.nix");
+        let mut server = server(&response, "\"😀\" builtins.map");
+
+        // The emoji takes two UTF-16 units but is a single character.
+        assert_eq!(server.hover(&at(0, 6))["range"], range(0, 5, 17));
+
+        server.initialize(
+            &json!({ "capabilities": { "general": { "positionEncodings": ["utf-32"] } } }),
+        );
+        assert_eq!(server.hover(&at(0, 5))["range"], range(0, 4, 16));
+    }
+
+    #[test]
+    fn completion_lists_members_after_a_dot() {
+        let response = response("/nowhere.nix");
+        let server = server(&response, "lib.\nlib.strings.con");
+
+        let items = server.completion(&at(0, 4));
+        let strings = items
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|i| i["label"] == "strings")
+            .unwrap();
+        assert_eq!(strings["kind"], COMPLETION_KIND_MODULE);
+
+        let items = server.completion(&at(1, 15));
+        let labels: Vec<&str> = items
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, ["concatStrings"]);
+    }
+
+    #[test]
+    fn definition_points_at_the_lambda() {
+        let dir = env::temp_dir().join(format!("noogle search lsp {}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("strings.nix");
+        fs::write(&file, "{\n  splitString = sep: s: s;\n}\n").unwrap();
+
+        let response = response(&file.display().to_string());
+        let server = server(&response, "splitString");
+        let location = server.definition(&at(0, 3));
+        let expected = fs::canonicalize(&file).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let uri = format!("file://{}", expected.display()).replace(' ', "%20");
+        assert_eq!(location["uri"], uri);
+        assert_eq!(location["range"], range(1, 4, 4));
+    }
+}
@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Context, Result, anyhow};
+use noogle_search::data::{self, NoogleResponse};
+use noogle_search::format::roff;
+use noogle_search::index::Index;
+use noogle_search::urls::SourceUrls;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+pub fn execute(
+    response: &NoogleResponse,
+    name: Option<&str>,
+    out_dir: Option<&Path>,
+) -> Result<()> {
+    let Some(name) = name else {
+        let out_dir = out_dir.ok_or_else(|| anyhow!("--all requires --out-dir"))?;
+        return write_all(response, out_dir);
+    };
+
+    let page = render(response, name)?;
+    match out_dir {
+        Some(dir) => {
+            let path = write_page(dir, name, &page)?;
+            eprintln!("Wrote {}", path.display());
+        }
+        None => print!("{}", page),
+    }
+
+    Ok(())
+}
+
+fn render(response: &NoogleResponse, name: &str) -> Result<String> {
+    if let Ok(doc) = super::util::find_doc(&Index::new(response), name) {
+        return Ok(roff::render_doc(
+            doc,
+            &response.upstream_info,
+            &SourceUrls::new(response),
+        ));
+    }
+
+    let counts = response.namespace_counts();
+    if counts.contains_key(name) {
+        let docs = response.docs_in_namespace(name);
+        let children = data::child_namespaces(&counts, name);
+        return Ok(roff::render_namespace(
+            name,
+            &docs,
+            &children,
+            &response.upstream_info,
+        ));
+    }
+
+    Err(anyhow!("No function or namespace named '{}'", name))
+}
+
+fn write_all(response: &NoogleResponse, out_dir: &Path) -> Result<()> {
+    let mut titles = HashSet::new();
+    let mut pages = 0;
+    let source_urls = SourceUrls::new(response);
+
+    for doc in &response.data {
+        write_page(
+            out_dir,
+            &doc.meta.title,
+            &roff::render_doc(doc, &response.upstream_info, &source_urls),
+        )?;
+        titles.insert(doc.meta.title.as_str());
+        pages += 1;
+    }
+
+    let counts = response.namespace_counts();
+    for namespace in counts.keys() {
+        if titles.contains(namespace.as_str()) {
+            continue;
+        }
+        let docs = response.docs_in_namespace(namespace);
+        let children = data::child_namespaces(&counts, namespace);
+        let page = roff::render_namespace(namespace, &docs, &children, &response.upstream_info);
+        write_page(out_dir, namespace, &page)?;
+        pages += 1;
+    }
+
+    let mut aliases = HashSet::new();
+    for doc in &response.data {
+        for name in doc.all_names().into_iter().skip(1) {
+            if titles.contains(name.as_str()) || !aliases.insert(name.clone()) {
+                continue;
+            }
+            write_page(
+                out_dir,
+                &name,
+                &roff::render_alias(&file_name(&doc.meta.title)),
+            )?;
+            pages += 1;
+        }
+    }
+
+    eprintln!(
+        "Wrote {} pages to {}",
+        pages,
+        out_dir.join(format!("man{}", roff::SECTION)).display()
+    );
+
+    Ok(())
+}
+
+fn write_page(out_dir: &Path, name: &str, page: &str) -> Result<std::path::PathBuf> {
+    let dir = out_dir.join(format!("man{}", roff::SECTION));
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{}.{}", file_name(name), roff::SECTION));
+    fs::write(&path, page).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn file_name(name: &str) -> String {
+    name.replace('/', "_")
+}
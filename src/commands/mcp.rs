@@ -0,0 +1,229 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::jsonrpc;
+use anyhow::Result;
+use noogle_search::data::Doc;
+use noogle_search::format::markdown;
+use noogle_search::index::{Child, Index, SearchHit, SearchMode};
+use noogle_search::urls::SourceUrls;
+use serde_json::{Value, json};
+use std::io;
+
+const PROTOCOL_VERSION: &str = "2025-06-18";
+/// Protocol revisions this server can speak, newest first.
+const SUPPORTED_VERSIONS: &[&str] = &[PROTOCOL_VERSION, "2025-03-26", "2024-11-05"];
+const DEFAULT_LIMIT: usize = 20;
+
+/// Speaks the Model Context Protocol over stdio until stdin closes.
+pub fn execute(index: &Index) -> Result<()> {
+    let mut reader = io::stdin().lock();
+    let mut writer = io::stdout().lock();
+
+    while let Some(message) = jsonrpc::read_line(&mut reader)? {
+        let message = match message {
+            Ok(message) => message,
+            Err(err) => {
+                jsonrpc::write_line(&mut writer, &jsonrpc::parse_error(&err))?;
+                continue;
+            }
+        };
+        let (Some(method), Some(id)) = (message["method"].as_str(), message.get("id")) else {
+            continue;
+        };
+
+        let params = &message["params"];
+        let reply = match method {
+            "initialize" => jsonrpc::response(id, initialize(params)),
+            "ping" => jsonrpc::response(id, json!({})),
+            "tools/list" => jsonrpc::response(id, json!({ "tools": tools() })),
+            "tools/call" => match call_tool(index, params) {
+                Some(result) => jsonrpc::response(id, result),
+                None => jsonrpc::error(id, jsonrpc::INVALID_PARAMS, "Unknown tool"),
+            },
+            _ => jsonrpc::error(id, jsonrpc::METHOD_NOT_FOUND, "Method not found"),
+        };
+        jsonrpc::write_line(&mut writer, &reply)?;
+    }
+
+    Ok(())
+}
+
+/// Agrees to the client's protocol version if supported, and otherwise offers the latest.
+fn initialize(params: &Value) -> Value {
+    let version = params["protocolVersion"]
+        .as_str()
+        .filter(|version| SUPPORTED_VERSIONS.contains(version))
+        .unwrap_or(PROTOCOL_VERSION);
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "instructions": "Look up Nix library functions (lib.*, builtins.*, pkgs.*) from Noogle \
+                         before using them; prefer get_function_doc to confirm a function exists.",
+    })
+}
+
+fn tools() -> Value {
+    json!([
+        {
+            "name": "search_functions",
+            "description": "Fuzzy search Nix functions by name, falling back to full-text search of their documentation.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Name fragment or words from the docs" },
+                    "limit": { "type": "integer", "description": "Maximum number of results", "default": DEFAULT_LIMIT },
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "get_function_doc",
+            "description": "Get the full documentation of a Nix function as markdown, by title or alias such as lib.strings.splitString or builtins.map.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Fully qualified function name" },
+                },
+                "required": ["name"],
+            },
+        },
+        {
+            "name": "list_namespace",
+            "description": "List the functions and sub-namespaces directly below a namespace such as lib.attrsets.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "namespace": { "type": "string", "description": "Dotted namespace path" },
+                },
+                "required": ["namespace"],
+            },
+        },
+        {
+            "name": "search_by_type",
+            "description": "Find Nix functions by type signature, e.g. \"string -> [string]\" or \"AttrSet -> AttrSet\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "type": { "type": "string", "description": "A type signature or fragment" },
+                    "limit": { "type": "integer", "description": "Maximum number of results", "default": DEFAULT_LIMIT },
+                },
+                "required": ["type"],
+            },
+        },
+    ])
+}
+
+/// Runs a tool; `None` when the tool does not exist.
+fn call_tool(index: &Index, params: &Value) -> Option<Value> {
+    let args = &params["arguments"];
+    let limit = args["limit"].as_u64().map_or(DEFAULT_LIMIT, |l| l as usize);
+    let source_urls = SourceUrls::new(index.response());
+
+    let result = match params["name"].as_str()? {
+        "search_functions" => match args["query"].as_str() {
+            Some(query) => Ok(hits_json(index.search(query, SearchMode::All, limit))),
+            None => Err("Missing argument 'query'".to_string()),
+        },
+        "get_function_doc" => match args["name"].as_str() {
+            Some(name) => index
+                .lookup(name)
+                .map(|doc| markdown::render_doc(doc, &source_urls))
+                .ok_or_else(|| format!("No function named '{}'", name)),
+            None => Err("Missing argument 'name'".to_string()),
+        },
+        "list_namespace" => match args["namespace"].as_str() {
+            Some(namespace) => list_namespace(index, namespace),
+            None => Err("Missing argument 'namespace'".to_string()),
+        },
+        "search_by_type" => match args["type"].as_str() {
+            Some(ty) => Ok(hits_json(index.search_by_type(ty, limit))),
+            None => Err("Missing argument 'type'".to_string()),
+        },
+        _ => return None,
+    };
+
+    Some(match result {
+        Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+        Err(message) => {
+            json!({ "content": [{ "type": "text", "text": message }], "isError": true })
+        }
+    })
+}
+
+fn list_namespace(index: &Index, namespace: &str) -> Result<String, String> {
+    let children = index.children(namespace);
+    if children.is_empty() {
+        return Err(format!("No namespace named '{}'", namespace));
+    }
+
+    let mut namespaces = Vec::new();
+    let mut functions = Vec::new();
+    for child in children {
+        match child {
+            Child::Namespace(name) => namespaces.push(json!(name)),
+            Child::Doc { name, doc } => functions.push(entry_json(&name, doc)),
+        }
+    }
+
+    Ok(pretty(
+        &json!({ "namespaces": namespaces, "functions": functions }),
+    ))
+}
+
+fn hits_json(hits: Vec<SearchHit>) -> String {
+    let entries: Vec<Value> = hits
+        .into_iter()
+        .map(|hit| entry_json(&hit.doc.meta.title, hit.doc))
+        .collect();
+    pretty(&json!(entries))
+}
+
+fn entry_json(name: &str, doc: &Doc) -> Value {
+    json!({
+        "name": name,
+        "signature": doc.meta.signature,
+        "summary": doc
+            .content
+            .as_ref()
+            .and_then(|c| c.content.as_deref())
+            .and_then(markdown::summary),
+    })
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initialize_negotiates_the_protocol_version() {
+        let version = |requested: Value| {
+            initialize(&json!({ "protocolVersion": requested }))["protocolVersion"].clone()
+        };
+        assert_eq!(version(json!("2025-03-26")), "2025-03-26");
+        assert_eq!(version(json!("1999-01-01")), PROTOCOL_VERSION);
+        assert_eq!(version(Value::Null), PROTOCOL_VERSION);
+    }
+}
@@ -20,3 +20,19 @@ pub mod search;
 pub mod open_source;
 pub mod open_noogle;
 pub mod util;
+pub mod man;
+pub mod export;
+pub mod lsp;
+pub mod serve;
+pub mod mcp;
+pub mod types;
+pub mod tree;
+pub mod edit;
+pub mod url;
+pub mod copy;
+pub mod index;
+pub mod examples;
+pub mod check_examples;
+pub mod coverage;
+pub mod refs;
+pub mod completions;
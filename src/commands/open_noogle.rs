@@ -14,18 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::NoogleResponse;
 use anyhow::Result;
-use std::process::Command;
+use noogle_search::data::NoogleResponse;
+use noogle_search::index::Index;
+use noogle_search::urls;
 
-pub fn execute(response: &NoogleResponse, input: &str) -> Result<()> {
-    let doc = super::util::find_doc(response, input)?;
-    let path = doc.meta.path.join("/");
-    let url = format!("https://noogle.dev/f/{}", path);
-    
-    Command::new("xdg-open")
-        .arg(&url)
-        .spawn()?;
+pub fn execute(response: &NoogleResponse, input: &str, browser: Option<&str>) -> Result<()> {
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, input)?;
+    let url = urls::noogle_url(doc);
+
+    super::util::open_url(&url, browser)?;
 
     Ok(())
 }
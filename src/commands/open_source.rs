@@ -14,36 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::NoogleResponse;
 use anyhow::{Result, anyhow};
-use std::process::Command;
+use noogle_search::data::NoogleResponse;
+use noogle_search::index::Index;
+use noogle_search::urls::SourceUrls;
 
-pub fn execute(response: &NoogleResponse, input: &str) -> Result<()> {
-    let doc = super::util::find_doc(response, input)?;
+pub fn execute(response: &NoogleResponse, input: &str, browser: Option<&str>) -> Result<()> {
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, input)?;
 
-    let position = doc
-        .meta
-        .lambda_position
-        .as_ref()
-        .or(doc.meta.attr_position.as_ref())
+    let url = SourceUrls::new(response)
+        .url(doc)
         .ok_or_else(|| anyhow!("No source position available"))?;
 
-    let rev = &response.upstream_info.rev;
-
-    let file_path = position
-        .file
-        .split("-source/")
-        .nth(1)
-        .unwrap_or(&position.file);
-
-    let url = format!(
-        "https://github.com/NixOS/nixpkgs/blob/{}/{}#L{}",
-        rev, file_path, position.line
-    );
-
     eprintln!("Opening: {}", url);
 
-    Command::new("xdg-open").arg(&url).spawn()?;
+    super::util::open_url(&url, browser)?;
 
     Ok(())
 }
@@ -14,12 +14,114 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::NoogleResponse;
-use crate::format;
 use anyhow::Result;
+use clap::ValueEnum;
+use noogle_search::data::{Doc, NoogleResponse};
+use noogle_search::format;
+use noogle_search::index::Index;
+use noogle_search::urls::SourceUrls;
+use noogle_search::{nixpkgs, related};
+use std::env;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum PreviewFormat {
+    /// Highlighted terminal output, coloured according to --color
+    #[default]
+    Terminal,
+    /// Terminal layout without any escape codes
+    Plain,
+    /// The cleaned-up markdown source
+    Markdown,
+    /// An HTML fragment
+    Html,
+    /// The full doc plus resolved URLs
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolves `auto` against `NO_COLOR`, `CLICOLOR_FORCE` and whether stdout is a terminal.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+                    return false;
+                }
+                if env::var_os("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0") {
+                    return true;
+                }
+                io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Where to read a definition's source from, and how many lines around it to show.
+pub struct SourceContext<'a> {
+    pub nixpkgs: Option<&'a Path>,
+    pub lines: usize,
+}
+
+pub fn execute(
+    response: &NoogleResponse,
+    input: &str,
+    output: PreviewFormat,
+    source: Option<SourceContext>,
+    color: bool,
+) -> Result<()> {
+    if let Some(namespace) = super::tree::namespace_selection(input) {
+        return super::tree::execute(response, Some(&namespace), Some(1));
+    }
+
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, input)?;
+    let source_urls = SourceUrls::new(response);
+
+    match output {
+        PreviewFormat::Terminal => print_terminal(&index, doc, source, color),
+        PreviewFormat::Plain => print_terminal(&index, doc, source, false),
+        PreviewFormat::Markdown => print!("{}", format::markdown::render_doc(doc, &source_urls)),
+        PreviewFormat::Html => print!("{}", format::html::render_doc(doc, &source_urls, None)),
+        PreviewFormat::Json => {
+            let preview = format::json::render_doc(doc, &source_urls);
+            println!("{}", serde_json::to_string_pretty(&preview)?);
+        }
+    }
 
-pub fn execute(response: &NoogleResponse, input: &str) -> Result<()> {
-    let doc = super::util::find_doc(response, input)?;
-    format::print_preview(doc);
     Ok(())
 }
+
+fn print_terminal(index: &Index, doc: &Doc, source: Option<SourceContext>, color: bool) {
+    let response = index.response();
+    print!(
+        "{}",
+        format::render_preview(doc, &response.builtin_types, index, color)
+    );
+    print!(
+        "{}",
+        format::render_see_also(&related::see_also(index, doc), color)
+    );
+
+    let Some(source) = source else {
+        return;
+    };
+    if let Some(position) = &doc.meta.lambda_position
+        && let Some(path) = nixpkgs::resolve(position, nixpkgs::checkout(response, source.nixpkgs))
+    {
+        print!(
+            "{}",
+            format::render_source(&path, position.line, source.lines, color)
+        );
+    }
+}
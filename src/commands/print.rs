@@ -14,10 +14,60 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::NoogleResponse;
+use clap::Args;
+use noogle_search::data::{Doc, NoogleResponse};
 
-pub fn execute(response: &NoogleResponse, filter: Option<&str>) {
-    for doc in &response.data {
+/// Restricts the list to builtins with the given primop metadata.
+#[derive(Debug, Clone, Default, Args)]
+pub struct PrimopFilter {
+    /// Only list builtins taking this many arguments
+    #[arg(long)]
+    pub arity: Option<u32>,
+
+    /// Only list builtins behind an experimental feature
+    #[arg(long, conflicts_with = "stable")]
+    pub experimental: bool,
+
+    /// Only list builtins that need no experimental feature
+    #[arg(long)]
+    pub stable: bool,
+}
+
+impl PrimopFilter {
+    fn is_active(&self) -> bool {
+        self.arity.is_some() || self.experimental || self.stable
+    }
+
+    fn matches(&self, doc: &Doc) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+        let Some(primop) = &doc.meta.primop_meta else {
+            return false;
+        };
+        self.arity.is_none_or(|arity| primop.arity == Some(arity))
+            && (!self.experimental || primop.experimental)
+            && (!self.stable || !primop.experimental)
+    }
+
+    /// The flags reproducing this filter on a `print` command line.
+    pub fn to_args(&self) -> String {
+        let mut args = String::new();
+        if let Some(arity) = self.arity {
+            args.push_str(&format!(" --arity {}", arity));
+        }
+        if self.experimental {
+            args.push_str(" --experimental");
+        }
+        if self.stable {
+            args.push_str(" --stable");
+        }
+        args
+    }
+}
+
+pub fn execute(response: &NoogleResponse, filter: Option<&str>, primops: &PrimopFilter) {
+    for doc in response.data.iter().filter(|doc| primops.matches(doc)) {
         if let Some(namespace) = filter {
             let prefix = format!("{}.", namespace);
             if let Some(stripped) = doc.meta.title.strip_prefix(&prefix) {
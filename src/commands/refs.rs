@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use noogle_search::data::{Doc, NoogleResponse};
+use noogle_search::index::Index;
+use noogle_search::related;
+
+/// Lists the functions `name`'s docs mention.
+pub fn refs(response: &NoogleResponse, name: &str) -> Result<()> {
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, name)?;
+    for target in related::references(doc, &index) {
+        println!("{}", target.meta.title);
+    }
+    Ok(())
+}
+
+/// Lists the functions whose docs mention `name` under any of its names.
+pub fn backrefs(response: &NoogleResponse, name: &str) -> Result<()> {
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, name)?;
+    for other in &response.data {
+        // A doc mentioning one of its own names is not a back-reference.
+        if same_function(other, doc) {
+            continue;
+        }
+        if related::references(other, &index)
+            .iter()
+            .any(|target| same_function(target, doc))
+        {
+            println!("{}", other.meta.title);
+        }
+    }
+    Ok(())
+}
+
+fn same_function(a: &Doc, b: &Doc) -> bool {
+    a.all_names().iter().any(|name| b.matches_name(name))
+}
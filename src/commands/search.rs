@@ -14,22 +14,32 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use super::print::PrimopFilter;
 use anyhow::Result;
 use std::env;
 use std::process::Command;
 
-pub fn execute(initial_filter: Option<String>, initial_query: Option<String>) -> Result<()> {
+pub fn execute(
+    initial_filter: Option<String>,
+    initial_query: Option<String>,
+    primops: &PrimopFilter,
+) -> Result<()> {
     let exe_path = env::current_exe()?;
 
     let initial_cmd = if let Some(filter) = &initial_filter {
-        format!("{} print --filter {}", exe_path.display(), filter)
+        format!(
+            "{} print --filter {}{}",
+            exe_path.display(),
+            filter,
+            primops.to_args()
+        )
     } else {
-        format!("{} print", exe_path.display())
+        format!("{} print{}", exe_path.display(), primops.to_args())
     };
 
     let mut fzf_args = vec![
         "--preview".to_string(),
-        format!("{} preview {{}}", exe_path.display()),
+        format!("{} --color always preview {{}}", exe_path.display()),
         "--preview-window=wrap".to_string(),
         "--scheme=history".to_string(),
         "--layout=reverse".to_string(),
@@ -40,17 +50,52 @@ pub fn execute(initial_filter: Option<String>, initial_query: Option<String>) ->
         "--bind".to_string(),
         format!("ctrl-n:execute({} open-noogle {{}})", exe_path.display()),
         "--bind".to_string(),
-        format!("ctrl-l:reload({} print --filter lib)", exe_path.display()),
+        format!("ctrl-e:execute({} edit {{}})", exe_path.display()),
+        "--bind".to_string(),
+        format!("ctrl-y:execute-silent({} copy name {{}})", exe_path.display()),
+        "--bind".to_string(),
+        format!("alt-y:execute-silent({} copy source {{}})", exe_path.display()),
+        "--bind".to_string(),
+        format!("alt-u:execute-silent({} copy usage {{}})", exe_path.display()),
+        "--bind".to_string(),
+        format!(
+            "ctrl-l:reload({} print --filter lib{})+change-prompt(> )",
+            exe_path.display(),
+            primops.to_args()
+        ),
+        "--bind".to_string(),
+        format!(
+            "ctrl-b:reload({} print --filter builtins{})+change-prompt(> )",
+            exe_path.display(),
+            primops.to_args()
+        ),
+        "--bind".to_string(),
+        format!(
+            "ctrl-p:reload({} print --filter pkgs{})+change-prompt(> )",
+            exe_path.display(),
+            primops.to_args()
+        ),
+        "--bind".to_string(),
+        format!(
+            "ctrl-a:reload({} print{})+change-prompt(> )",
+            exe_path.display(),
+            primops.to_args()
+        ),
         "--bind".to_string(),
-        format!("ctrl-b:reload({} print --filter builtins)", exe_path.display()),
+        format!(
+            "ctrl-t:reload({} browse list {})+change-prompt({})",
+            exe_path.display(),
+            initial_filter.as_deref().unwrap_or("''"),
+            super::tree::prompt(initial_filter.as_deref().unwrap_or(""))
+        ),
         "--bind".to_string(),
-        format!("ctrl-p:reload({} print --filter pkgs)", exe_path.display()),
+        format!("enter:transform({} browse enter {{}})", exe_path.display()),
         "--bind".to_string(),
-        format!("ctrl-a:reload({} print)", exe_path.display()),
+        format!("bspace:transform({} browse back {{q}})", exe_path.display()),
         "--bind".to_string(),
         "ctrl-/:toggle-preview".to_string(),
         "--header".to_string(),
-        "Ctrl-L: lib | Ctrl-B: builtins | Ctrl-P: pkgs | Ctrl-A: all\nCtrl-O: source | Ctrl-N: noogle | Ctrl-/: preview".to_string(),
+        "Ctrl-L: lib | Ctrl-B: builtins | Ctrl-P: pkgs | Ctrl-A: all | Ctrl-T: tree (Enter: open, Bspace: up)\nCtrl-O: source | Ctrl-E: edit | Ctrl-N: noogle | Ctrl-/: preview\nCtrl-Y: copy name | Alt-Y: copy URL | Alt-U: copy usage".to_string(),
     ];
 
     if let Some(query) = initial_query {
@@ -59,7 +104,7 @@ pub fn execute(initial_filter: Option<String>, initial_query: Option<String>) ->
     }
 
     let mut child = Command::new("fzf")
-        .args(&fzf_args)
+        .args(fzf_args)
         .stdin(
             Command::new("sh")
                 .arg("-c")
@@ -0,0 +1,421 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Context, Result};
+use noogle_search::data::Doc;
+use noogle_search::format::{self, markdown};
+use noogle_search::index::{Child, Index, SearchMode};
+use noogle_search::related;
+use noogle_search::urls::SourceUrls;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 500;
+const MAX_HEADER_BYTES: u64 = 8 * 1024;
+const TIMEOUT: Duration = Duration::from_secs(10);
+const WORKERS: usize = 4;
+
+struct Reply {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Reply {
+    fn json(status: u16, value: Value) -> Self {
+        Reply {
+            status,
+            content_type: "application/json",
+            body: value.to_string(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Reply::json(status, json!({ "error": message }))
+    }
+}
+
+/// Serves the JSON API on `listen` until the process is killed, with `WORKERS` threads
+/// taking turns accepting connections.
+///
+/// Cross-origin requests are only allowed from `cors`, when given.
+pub fn execute(index: &Index, listen: &str, cors: Option<&str>) -> Result<()> {
+    let listener =
+        TcpListener::bind(listen).with_context(|| format!("Failed to listen on {}", listen))?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+
+    thread::scope(|scope| {
+        for _ in 0..WORKERS {
+            let listener = &listener;
+            scope.spawn(move || {
+                for stream in listener.incoming() {
+                    let result = stream
+                        .map_err(Into::into)
+                        .and_then(|stream| handle(index, stream, cors));
+                    if let Err(e) = result {
+                        eprintln!("Request failed: {:#}", e);
+                    }
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+fn handle(index: &Index, mut stream: TcpStream, cors: Option<&str>) -> Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let reply = match read_request_line(&stream)? {
+        Some(request_line) => {
+            let mut parts = request_line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some("GET"), Some(target)) => route(index, target),
+                (Some(_), Some(_)) => Reply::error(405, "Only GET is supported"),
+                _ => Reply::error(400, "Malformed request"),
+            }
+        }
+        None => Reply::error(431, "Request headers too large"),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\n",
+        reply.status,
+        reason(reply.status),
+        reply.content_type,
+        reply.body.len()
+    )?;
+    if let Some(origin) = cors {
+        write!(stream, "Access-Control-Allow-Origin: {}\r\n", origin)?;
+    }
+    write!(stream, "Connection: close\r\n\r\n")?;
+    stream.write_all(reply.body.as_bytes())?;
+    Ok(())
+}
+
+/// Reads the request line and skips the headers, or `None` if they don't fit in
+/// `MAX_HEADER_BYTES`.
+fn read_request_line(stream: &TcpStream) -> Result<Option<String>> {
+    let mut reader = BufReader::new(stream.take(MAX_HEADER_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            // Either the client closed early or the limit cut the headers off.
+            let truncated = reader.into_inner().limit() == 0;
+            return Ok((!truncated).then_some(request_line));
+        }
+        if header.trim().is_empty() {
+            return Ok(Some(request_line));
+        }
+    }
+}
+
+fn route(index: &Index, target: &str) -> Reply {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let params = parse_query(query);
+    let segments: Vec<String> = path
+        .trim_matches('/')
+        .split('/')
+        .map(|s| percent_decode(s, false))
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    match segments.as_slice() {
+        ["api", "v1", "functions", name] => match index.lookup(name) {
+            Some(doc) => {
+                let source_urls = SourceUrls::new(index.response());
+                Reply::json(200, json!(format::json::render_doc(doc, &source_urls)))
+            }
+            None => Reply::error(404, &format!("Function '{}' not found", name)),
+        },
+        ["api", "v1", "search"] => search(index, &params),
+        ["api", "v1", "namespaces"] => namespaces(index),
+        ["api", "v1", "namespaces", namespace] => namespace_children(index, namespace),
+        ["api", "v1", "preview", name] => preview(index, name, &params),
+        _ => Reply::error(404, "Unknown endpoint"),
+    }
+}
+
+fn search(index: &Index, params: &HashMap<String, String>) -> Reply {
+    let Some(query) = params.get("q") else {
+        return Reply::error(400, "Missing query parameter 'q'");
+    };
+    let mode = match params.get("mode").map(String::as_str) {
+        None | Some("all") => SearchMode::All,
+        Some("fuzzy") => SearchMode::Fuzzy,
+        Some("text") => SearchMode::Text,
+        Some(other) => return Reply::error(400, &format!("Unknown search mode '{}'", other)),
+    };
+    let limit = params
+        .get("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(DEFAULT_LIMIT)
+        .min(MAX_LIMIT);
+
+    let hits: Vec<Value> = index
+        .search(query, mode, limit)
+        .into_iter()
+        .map(|hit| {
+            let mut entry = summary_json(&hit.doc.meta.title, hit.doc);
+            entry["score"] = json!(hit.score);
+            entry
+        })
+        .collect();
+
+    Reply::json(200, json!(hits))
+}
+
+fn namespaces(index: &Index) -> Reply {
+    let namespaces: Vec<Value> = index
+        .response()
+        .namespace_counts()
+        .into_iter()
+        .map(|(name, functions)| json!({ "name": name, "functions": functions }))
+        .collect();
+
+    Reply::json(200, json!(namespaces))
+}
+
+fn namespace_children(index: &Index, namespace: &str) -> Reply {
+    let children = index.children(namespace);
+    if children.is_empty() {
+        return Reply::error(404, &format!("Namespace '{}' not found", namespace));
+    }
+
+    let mut namespaces = Vec::new();
+    let mut functions = Vec::new();
+    for child in children {
+        match child {
+            Child::Namespace(name) => namespaces.push(json!(name)),
+            Child::Doc { name, doc } => functions.push(summary_json(&name, doc)),
+        }
+    }
+
+    Reply::json(
+        200,
+        json!({ "namespace": namespace, "namespaces": namespaces, "functions": functions }),
+    )
+}
+
+fn preview(index: &Index, name: &str, params: &HashMap<String, String>) -> Reply {
+    let Some(doc) = index.lookup(name) else {
+        return Reply::error(404, &format!("Function '{}' not found", name));
+    };
+    let source_urls = SourceUrls::new(index.response());
+    let types = &index.response().builtin_types;
+    let terminal = |color| {
+        let see_also = related::see_also(index, doc);
+        format::render_preview(doc, types, index, color)
+            + &format::render_see_also(&see_also, color)
+    };
+
+    let (content_type, body) = match params.get("format").map(String::as_str) {
+        None | Some("markdown") => ("text/markdown", markdown::render_doc(doc, &source_urls)),
+        Some("html") => (
+            "text/html",
+            format::html::render_doc(doc, &source_urls, None),
+        ),
+        Some("ansi") => ("text/plain", terminal(true)),
+        Some("plain") => ("text/plain", terminal(false)),
+        Some(other) => return Reply::error(400, &format!("Unknown preview format '{}'", other)),
+    };
+
+    Reply {
+        status: 200,
+        content_type,
+        body,
+    }
+}
+
+fn summary_json(name: &str, doc: &Doc) -> Value {
+    json!({
+        "name": name,
+        "title": doc.meta.title,
+        "signature": doc.meta.signature,
+        "summary": doc
+            .content
+            .as_ref()
+            .and_then(|c| c.content.as_deref())
+            .and_then(markdown::summary),
+    })
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key, true), percent_decode(value, true))
+        })
+        .collect()
+}
+
+/// Decodes `%XX` escapes, and `+` as a space inside query strings.
+fn percent_decode(text: &str, plus_as_space: bool) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 3;
+                    }
+                    None => {
+                        out.push(b'%');
+                        i += 1;
+                    }
+                }
+            }
+            b'+' if plus_as_space => {
+                out.push(b' ');
+                i += 1;
+            }
+            byte => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use noogle_search::data::NoogleResponse;
+
+    fn response() -> NoogleResponse {
+        serde_json::from_value(json!({
+            "data": [
+                {
+                    "meta": {
+                        "title": "lib.strings.splitString",
+                        "path": ["lib", "strings", "splitString"],
+                        "is_primop": false,
+                    },
+                    "content": { "content": "Cut a string with a separator." },
+                },
+                {
+                    "meta": { "title": "builtins.map", "path": ["builtins", "map"], "is_primop": true },
+                    "content": { "content": "Apply a function to every element of a list." },
+                },
+            ],
+            "builtinTypes": {},
+            "upstreamInfo": { "rev": "abc", "lastModified": 0 },
+            "nixInfo": { "rev": "abc", "lastModified": 0 },
+        }))
+        .unwrap()
+    }
+
+    fn body(reply: &Reply) -> Value {
+        serde_json::from_str(&reply.body).unwrap()
+    }
+
+    #[test]
+    fn percent_escapes_are_decoded() {
+        assert_eq!(
+            percent_decode("lib.strings%2EsplitString", false),
+            "lib.strings.splitString"
+        );
+        assert_eq!(percent_decode("a+b%20c", true), "a b c");
+        assert_eq!(percent_decode("a+b", false), "a+b");
+        assert_eq!(percent_decode("%C3%A9", false), "é");
+    }
+
+    #[test]
+    fn bad_percent_escapes_are_kept_literally() {
+        assert_eq!(percent_decode("100%", false), "100%");
+        assert_eq!(percent_decode("%zz%4", false), "%zz%4");
+        assert_eq!(percent_decode("%FF", false), "\u{FFFD}");
+    }
+
+    #[test]
+    fn query_strings_are_split_into_decoded_pairs() {
+        let params = parse_query("q=split+string&mode=fuzzy&&flag&x%3Dy=%26");
+        assert_eq!(params["q"], "split string");
+        assert_eq!(params["mode"], "fuzzy");
+        assert_eq!(params["flag"], "");
+        assert_eq!(params["x=y"], "&");
+        assert!(parse_query("").is_empty());
+    }
+
+    #[test]
+    fn routes_reach_their_endpoints() {
+        let response = response();
+        let index = Index::new(&response);
+
+        let reply = route(&index, "/api/v1/functions/builtins%2Emap");
+        assert_eq!(reply.status, 200);
+        assert_eq!(body(&reply)["meta"]["title"], "builtins.map");
+
+        let reply = route(&index, "/api/v1/search?q=splitString&limit=1");
+        assert_eq!(reply.status, 200);
+        assert_eq!(body(&reply)[0]["title"], "lib.strings.splitString");
+
+        let reply = route(&index, "/api/v1/namespaces/lib.strings");
+        assert_eq!(
+            body(&reply)["functions"][0]["name"],
+            "lib.strings.splitString"
+        );
+
+        let reply = route(&index, "/api/v1/preview/builtins.map?format=plain");
+        assert_eq!(reply.content_type, "text/plain");
+        assert!(reply.body.contains("every element"));
+    }
+
+    #[test]
+    fn unknown_paths_and_bad_parameters_are_errors() {
+        let response = response();
+        let index = Index::new(&response);
+
+        assert_eq!(route(&index, "/api/v2/search?q=map").status, 404);
+        assert_eq!(route(&index, "/api/v1/functions/nope").status, 404);
+        assert_eq!(route(&index, "/api/v1/namespaces/nope").status, 404);
+        assert_eq!(route(&index, "/api/v1/search").status, 400);
+        assert_eq!(route(&index, "/api/v1/search?q=map&mode=exact").status, 400);
+        assert_eq!(
+            route(&index, "/api/v1/preview/builtins.map?format=pdf").status,
+            400
+        );
+    }
+}
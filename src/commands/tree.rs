@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Result, bail};
+use clap::Subcommand;
+use noogle_search::data::{self, NoogleResponse};
+use std::collections::BTreeMap;
+use std::env;
+
+/// Steps of the picker's namespace drill-down, driven by fzf `transform` bindings.
+#[derive(Subcommand)]
+pub enum BrowseAction {
+    /// Print the entries directly below a namespace
+    List { namespace: Option<String> },
+    /// Enter the selected namespace, or accept a selected function
+    Enter { selection: String },
+    /// Go up one namespace when the query is empty
+    Back { query: Option<String> },
+}
+
+/// Prints the namespace hierarchy below `root` with the number of functions in each.
+pub fn execute(response: &NoogleResponse, root: Option<&str>, depth: Option<usize>) -> Result<()> {
+    let counts = response.namespace_counts();
+    let root = root.unwrap_or("");
+
+    if root.is_empty() {
+        let top = data::child_namespaces(&counts, "");
+        for (i, namespace) in top.iter().enumerate() {
+            println!("{} ({})", namespace, counts[namespace]);
+            print_children(&counts, namespace, "", depth.map(|d| d.saturating_sub(1)));
+            if i + 1 < top.len() {
+                println!();
+            }
+        }
+    } else {
+        let Some(count) = counts.get(root) else {
+            bail!("Namespace '{}' not found", root);
+        };
+        println!("{} ({})", root, count);
+        print_children(&counts, root, "", depth);
+    }
+
+    Ok(())
+}
+
+fn print_children(
+    counts: &BTreeMap<String, usize>,
+    namespace: &str,
+    indent: &str,
+    depth: Option<usize>,
+) {
+    if depth == Some(0) {
+        return;
+    }
+
+    let children = data::child_namespaces(counts, namespace);
+    for (i, child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let segment = child.rsplit('.').next().unwrap_or(child);
+        println!(
+            "{}{} {} ({})",
+            indent,
+            if last { "└──" } else { "├──" },
+            segment,
+            counts[child]
+        );
+        let indent = format!("{}{}", indent, if last { "    " } else { "│   " });
+        print_children(counts, child, &indent, depth.map(|d| d - 1));
+    }
+}
+
+/// Runs one drill-down step; only listing needs the data, so `load` is not
+/// called for the Enter and Backspace bindings.
+pub fn browse(action: BrowseAction, load: impl FnOnce() -> Result<NoogleResponse>) -> Result<()> {
+    let exe_path = env::current_exe()?;
+
+    match action {
+        BrowseAction::List { namespace } => {
+            print_level(&load()?, namespace.as_deref().unwrap_or(""))
+        }
+        BrowseAction::Enter { selection } => match namespace_selection(&selection) {
+            Some(namespace) => println!("{}", enter(&exe_path.display().to_string(), &namespace)),
+            None => println!("accept"),
+        },
+        BrowseAction::Back { query } => {
+            let current = current_namespace();
+            if query.is_some_and(|q| !q.is_empty()) || current.is_empty() {
+                println!("backward-delete-char");
+            } else {
+                let parent = current.rsplit_once('.').map_or("", |(parent, _)| parent);
+                println!("{}", enter(&exe_path.display().to_string(), parent));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists sub-namespaces as `segment/` followed by functions, in `print --filter` form.
+fn print_level(response: &NoogleResponse, namespace: &str) {
+    let line = |segment: &str| {
+        if namespace.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}\t{}", segment, namespace)
+        }
+    };
+
+    for child in data::child_namespaces(&response.namespace_counts(), namespace) {
+        let segment = child.rsplit('.').next().unwrap_or(&child);
+        println!("{}", line(&format!("{}/", segment)));
+    }
+    for doc in response.docs_in_namespace(namespace) {
+        if let Some(segment) = doc.meta.path.last() {
+            println!("{}", line(segment));
+        }
+    }
+}
+
+/// The full namespace named by a `segment/` picker line, if it is one.
+pub fn namespace_selection(selection: &str) -> Option<String> {
+    let (name, parent) = super::util::parse_input(selection);
+    let segment = name.strip_suffix('/')?;
+    Some(match parent {
+        Some(parent) if !parent.is_empty() => format!("{}.{}", parent, segment),
+        _ => segment.to_string(),
+    })
+}
+
+/// fzf actions switching the picker to `namespace`.
+fn enter(exe: &str, namespace: &str) -> String {
+    format!(
+        "reload({} browse list '{}')+change-prompt({})+clear-query",
+        exe,
+        namespace,
+        prompt(namespace)
+    )
+}
+
+/// The picker prompt, which doubles as the drill-down state.
+pub fn prompt(namespace: &str) -> String {
+    if namespace.is_empty() {
+        "> ".to_string()
+    } else {
+        format!("{}> ", namespace)
+    }
+}
+
+fn current_namespace() -> String {
+    env::var("FZF_PROMPT")
+        .unwrap_or_default()
+        .trim_end()
+        .trim_end_matches('>')
+        .to_string()
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use noogle_search::data::NoogleResponse;
+use noogle_search::format;
+
+/// Lists builtin types as `key<TAB>name<TAB>description`, optionally only those matching `query`.
+pub fn execute(response: &NoogleResponse, query: Option<&str>) {
+    let query = query.map(str::to_lowercase);
+
+    for (key, ty) in &response.builtin_types {
+        if let Some(query) = &query {
+            let haystack = format!("{} {} {}", key, ty.name, ty.description).to_lowercase();
+            if !haystack.contains(query.as_str()) {
+                continue;
+            }
+        }
+        let description = format::strip_inline_code(&ty.description).replace('\n', " ");
+        println!("{}\t{}\t{}", key, ty.name, description);
+    }
+}
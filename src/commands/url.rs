@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use noogle_search::data::{Doc, NoogleResponse};
+use noogle_search::index::Index;
+use noogle_search::urls::{self, SourceUrls};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UrlKind {
+    /// The definition in the nixpkgs repository
+    Source,
+    /// The function's page on noogle.dev
+    Noogle,
+}
+
+pub fn url_for(response: &NoogleResponse, doc: &Doc, kind: UrlKind) -> Result<String> {
+    match kind {
+        UrlKind::Source => SourceUrls::new(response)
+            .url(doc)
+            .ok_or_else(|| anyhow!("No source link available")),
+        UrlKind::Noogle => Ok(urls::noogle_url(doc)),
+    }
+}
+
+pub fn execute(response: &NoogleResponse, kind: UrlKind, input: &str) -> Result<()> {
+    let index = Index::new(response);
+    let doc = super::util::find_doc(&index, input)?;
+    println!("{}", url_for(response, doc, kind)?);
+    Ok(())
+}
@@ -14,8 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::{Doc, NoogleResponse};
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
+use noogle_search::data::Doc;
+use noogle_search::index::Index;
+use std::env;
+use std::process::Command;
 
 pub fn parse_input(input: &str) -> (&str, Option<&str>) {
     if input.contains('\t') {
@@ -26,19 +29,126 @@ pub fn parse_input(input: &str) -> (&str, Option<&str>) {
     }
 }
 
-pub fn find_doc<'a>(response: &'a NoogleResponse, input: &str) -> Result<&'a Doc> {
+pub fn find_doc<'a>(index: &Index<'a>, input: &str) -> Result<&'a Doc> {
     let (name, filter) = parse_input(input);
-    
+
     let full_name = if let Some(ns) = filter {
         format!("{}.{}", ns, name)
     } else {
         name.to_string()
     };
 
-    response
-        .data
-        .iter()
-        .find(|d| d.meta.title == full_name)
-        .or_else(|| response.data.iter().find(|d| d.matches_name(&full_name)))
+    index
+        .lookup(&full_name)
         .ok_or_else(|| anyhow!("Function '{}' not found", full_name))
 }
+
+/// Opens `url` with `browser`, then `$BROWSER`, then `xdg-open`; `%s` in the command marks the URL.
+pub fn open_url(url: &str, browser: Option<&str>) -> Result<()> {
+    let browser = browser
+        .map(str::to_string)
+        .or_else(|| env::var("BROWSER").ok().filter(|b| !b.is_empty()))
+        .unwrap_or_else(|| "xdg-open".to_string());
+
+    let args = browser_args(&browser, url)?;
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| anyhow!("Browser command is empty"))?;
+
+    Command::new(program).args(args).spawn().with_context(|| {
+        format!(
+            "Failed to launch {}; use `noogle-search url` to print the URL instead",
+            program
+        )
+    })?;
+
+    Ok(())
+}
+
+/// The words of a browser command with `%s` replaced by `url`, or `url` appended.
+fn browser_args(browser: &str, url: &str) -> Result<Vec<String>> {
+    let mut words = split_words(browser)?;
+    if !words.iter().any(|w| w.contains("%s")) {
+        words.push(url.to_string());
+    }
+    Ok(words.iter().map(|w| w.replace("%s", url)).collect())
+}
+
+/// Splits a command line on whitespace like a shell would, honouring single and
+/// double quotes and backslash escapes, but without any expansion.
+fn split_words(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let word = word.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => bail!("Unterminated quote in browser command"),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => bail!("Unterminated quote in browser command"),
+                        },
+                        Some(c) => word.push(c),
+                        None => bail!("Unterminated quote in browser command"),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    word.get_or_insert_default().push(c);
+                }
+            }
+            c if c.is_whitespace() => words.extend(word.take()),
+            c => word.get_or_insert_default().push(c),
+        }
+    }
+    words.extend(word);
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn browser_commands_take_the_url_at_percent_s() {
+        let url = "https://noogle.dev/f/lib/id";
+        assert_eq!(browser_args("firefox", url).unwrap(), ["firefox", url]);
+        assert_eq!(
+            browser_args("firefox --new-tab %s", url).unwrap(),
+            ["firefox", "--new-tab", url]
+        );
+        assert_eq!(
+            browser_args("open -a Safari --args url=%s", url).unwrap(),
+            ["open", "-a", "Safari", "--args", &format!("url={}", url)]
+        );
+    }
+
+    #[test]
+    fn browser_commands_may_be_quoted() {
+        assert_eq!(
+            split_words(r#"'/opt/My Browser/browser' "--title=a \"b\"" c\ d ''"#).unwrap(),
+            ["/opt/My Browser/browser", r#"--title=a "b""#, "c d", ""]
+        );
+        assert!(split_words("'open").is_err());
+    }
+}
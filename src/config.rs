@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::sources::DocSource;
+use crate::urls::SourceLinks;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+const CONFIG_DIR_NAME: &str = "noogle-search";
+const CONFIG_FILE: &str = "config.json";
+
+/// User settings read from `~/.config/noogle-search/config.json`; every field is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// A local nixpkgs checkout used to resolve source positions.
+    pub nixpkgs: Option<PathBuf>,
+    /// Lines of context around a definition to show from its local source in previews.
+    pub source_context: Option<usize>,
+    /// The forge and repository that source links point at.
+    pub source_links: SourceLinks,
+    /// Additional doc sources merged into the Noogle data.
+    pub sources: Vec<DocSource>,
+    /// Command evaluating a Nix expression passed as its last argument, for `check-examples`.
+    pub eval_command: Option<Vec<String>>,
+}
+
+pub fn load_config() -> Result<Config> {
+    let Some(config_dir) = dirs::config_dir() else {
+        return Ok(Config::default());
+    };
+    let path = config_dir.join(CONFIG_DIR_NAME).join(CONFIG_FILE);
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
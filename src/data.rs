@@ -14,18 +14,30 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::urls::{NamespaceLinks, SourceLinks};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+use std::path::PathBuf;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct NoogleResponse {
     pub data: Vec<Doc>,
-    #[serde(rename = "builtinTypes")]
-    pub builtin_types: HashMap<String, serde_json::Value>,
+    #[serde(rename = "builtinTypes", deserialize_with = "builtin_types")]
+    pub builtin_types: BTreeMap<String, BuiltinType>,
     #[serde(rename = "upstreamInfo")]
     pub upstream_info: RepoInfo,
     #[serde(rename = "nixInfo")]
     pub nix_info: RepoInfo,
+    /// Source links for the docs merged in from additional sources.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub namespace_links: Vec<NamespaceLinks>,
+    /// Where source links for nixpkgs docs point; set from the config rather than stored.
+    #[serde(skip)]
+    pub source_links: SourceLinks,
+    /// The nixpkgs checkout an offline index was built from; its positions are relative to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkout: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -42,7 +54,7 @@ pub struct DocMeta {
     pub aliases: Option<Vec<Vec<String>>>,
     pub signature: Option<String>,
     pub is_primop: bool,
-    pub primop_meta: Option<serde_json::Value>,
+    pub primop_meta: Option<PrimopMeta>,
     pub is_functor: Option<bool>,
     pub attr_position: Option<Position>,
     pub attr_expr: Option<String>,
@@ -50,6 +62,82 @@ pub struct DocMeta {
     pub lambda_expr: Option<String>,
     pub count_applied: Option<u32>,
     pub content_meta: Option<ContentMeta>,
+    /// Set for module options rather than functions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub option: Option<OptionMeta>,
+}
+
+/// The declaration details of a NixOS or Home Manager module option.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OptionMeta {
+    #[serde(rename = "type")]
+    pub option_type: Option<String>,
+    pub default: Option<OptionValue>,
+    pub example: Option<OptionValue>,
+    #[serde(default)]
+    pub declarations: Vec<Declaration>,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// An option's default or example: Nix code, or Markdown prose from a `literalMD`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", content = "text", rename_all = "lowercase")]
+pub enum OptionValue {
+    Nix(String),
+    Markdown(String),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Declaration {
+    pub name: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PrimopMeta {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub args: Vec<String>,
+    pub arity: Option<u32>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub experimental: bool,
+    /// The `experimental-features` entry that must be enabled to use the primop.
+    #[serde(default)]
+    pub experimental_feature: Option<String>,
+}
+
+/// Deserializes an explicit `null` like a missing field.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// A type used in builtin signatures, keyed by the identifier the signatures use.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BuiltinType {
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub name: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub description: String,
+}
+
+/// Builtin types by key; an entry without a name is named after its key.
+fn builtin_types<'de, D>(deserializer: D) -> Result<BTreeMap<String, BuiltinType>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut types = BTreeMap::<String, BuiltinType>::deserialize(deserializer)?;
+    for (key, ty) in &mut types {
+        if ty.name.is_empty() {
+            ty.name = key.clone();
+        }
+    }
+    Ok(types)
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -88,7 +176,78 @@ pub struct RepoInfo {
     pub last_modified: u64,
 }
 
+impl NoogleResponse {
+    /// Every namespace that contains at least one doc, e.g. `lib` and `lib.strings`.
+    pub fn namespaces(&self) -> BTreeSet<String> {
+        self.namespace_counts().into_keys().collect()
+    }
+
+    /// Number of docs anywhere below each namespace, following `meta.path`.
+    pub fn namespace_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for doc in &self.data {
+            for len in 1..doc.meta.path.len() {
+                *counts.entry(doc.meta.path[..len].join(".")).or_default() += 1;
+            }
+        }
+        counts
+    }
+
+    /// Docs whose path is directly below `namespace`.
+    pub fn docs_in_namespace(&self, namespace: &str) -> Vec<&Doc> {
+        self.data
+            .iter()
+            .filter(|doc| doc.namespace() == namespace)
+            .collect()
+    }
+}
+
+/// Namespaces directly below `namespace` among the keys of `counts`, as returned by
+/// [`NoogleResponse::namespace_counts`]; the empty string lists the top level.
+///
+/// Only the sorted range of `namespace`'s descendants is visited, so walking the whole
+/// tree doesn't recount the docs at every level.
+pub fn child_namespaces(counts: &BTreeMap<String, usize>, namespace: &str) -> Vec<String> {
+    let prefix = if namespace.is_empty() {
+        String::new()
+    } else {
+        format!("{}.", namespace)
+    };
+    counts
+        .range::<str, _>((Bound::Included(prefix.as_str()), Bound::Unbounded))
+        .map(|(name, _)| name)
+        .take_while(|name| name.starts_with(&prefix))
+        .filter(|name| !name[prefix.len()..].contains('.'))
+        .cloned()
+        .collect()
+}
+
 impl Doc {
+    /// The dotted path of the namespace containing this doc.
+    pub fn namespace(&self) -> String {
+        let path = &self.meta.path;
+        path[..path.len().saturating_sub(1)].join(".")
+    }
+
+    /// Builtin types mentioned in the signature, in order of first mention.
+    pub fn signature_types<'a>(
+        &self,
+        types: &'a BTreeMap<String, BuiltinType>,
+    ) -> Vec<(&'a str, &'a BuiltinType)> {
+        let Some(signature) = &self.meta.signature else {
+            return Vec::new();
+        };
+        let mut found: Vec<(&str, &BuiltinType)> = Vec::new();
+        for word in signature.split(|c: char| !c.is_alphanumeric()) {
+            if let Some((key, ty)) = types.get_key_value(word)
+                && !found.iter().any(|(k, _)| *k == key)
+            {
+                found.push((key, ty));
+            }
+        }
+        found
+    }
+
     pub fn all_names(&self) -> Vec<String> {
         let mut names = vec![self.meta.title.clone()];
         if let Some(aliases) = &self.meta.aliases {
@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::Doc;
+use crate::format::markdown::{Block, parse_blocks};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// One example from a doc's `# Examples` section.
+#[derive(Debug, Clone, Serialize)]
+pub struct Example {
+    pub function: String,
+    /// Position among the function's examples, starting at 1.
+    pub index: usize,
+    pub expression: String,
+    /// The value after `=>`, when the example states one.
+    pub expected: Option<String>,
+}
+
+/// Extracts the examples from the code blocks below a doc's `Examples` heading.
+///
+/// A block holding several `expression` / `=> result` pairs yields one example per pair.
+pub fn extract(doc: &Doc) -> Vec<Example> {
+    let Some(text) = doc.content.as_ref().and_then(|c| c.content.as_deref()) else {
+        return Vec::new();
+    };
+
+    let mut examples = Vec::new();
+    let mut section_level = None;
+
+    for block in parse_blocks(text) {
+        match block {
+            Block::Heading { level, text } => {
+                let title = text.trim_matches('`').to_ascii_lowercase();
+                if title == "examples" || title == "example" {
+                    section_level = Some(level);
+                } else if section_level.is_some_and(|section| level <= section) {
+                    section_level = None;
+                }
+            }
+            Block::Code { lang, code }
+                if section_level.is_some() && matches!(lang.as_str(), "" | "nix") =>
+            {
+                for (expression, expected) in split_pairs(&code) {
+                    examples.push(Example {
+                        function: doc.meta.title.clone(),
+                        index: examples.len() + 1,
+                        expression,
+                        expected,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    examples
+}
+
+/// Splits a code block into expressions and the `=>` results following them.
+fn split_pairs(code: &str) -> Vec<(String, Option<String>)> {
+    let mut pairs = Vec::new();
+    let mut expression: Vec<&str> = Vec::new();
+    let mut expected: Option<Vec<&str>> = None;
+
+    for line in code.lines() {
+        if let Some(result) = line.trim_start().strip_prefix("=>") {
+            if let Some(previous) = expected.take() {
+                pairs.push((expression.join("\n"), Some(previous.join("\n"))));
+                expression.clear();
+            }
+            expected = Some(vec![result.trim()]);
+        } else if let Some(lines) = &mut expected {
+            if !balanced(&lines.join("\n")) {
+                lines.push(line);
+            } else if !line.trim().is_empty() {
+                pairs.push((expression.join("\n"), Some(lines.join("\n"))));
+                expression = vec![line];
+                expected = None;
+            }
+        } else if !line.trim().is_empty() || !expression.is_empty() {
+            expression.push(line);
+        }
+    }
+
+    match expected {
+        Some(lines) => pairs.push((expression.join("\n"), Some(lines.join("\n")))),
+        None if expression.iter().any(|l| !l.trim().is_empty()) => {
+            pairs.push((expression.join("\n").trim_end().to_string(), None))
+        }
+        None => {}
+    }
+
+    pairs
+        .into_iter()
+        .filter(|(expression, _)| !expression.trim().is_empty())
+        .collect()
+}
+
+/// Whether every bracket opened in `text` is closed, ignoring string contents.
+fn balanced(text: &str) -> bool {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in text.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    depth <= 0 && !in_string
+}
+
+/// Brings the function's namespace into scope, as the examples call it unqualified.
+///
+/// `lib` and `pkgs` are bound lazily, so only examples using them need `<nixpkgs>`.
+pub fn scope(doc: &Doc) -> String {
+    let namespace = doc.namespace();
+    if namespace.is_empty() {
+        "with lib;".to_string()
+    } else {
+        format!("with lib; with {};", namespace)
+    }
+}
+
+const PRELUDE: &str = "let\n  pkgs = import <nixpkgs> { };\n  lib = import <nixpkgs/lib>;\nin\n";
+
+/// What [`check_expression`] evaluates to when the example holds.
+pub const PASS: &str = "\"noogle-search: pass\"";
+
+/// An expression evaluating to [`PASS`] when the example's result matches, or to the
+/// actual value otherwise; `None` for examples without an expected result.
+pub fn check_expression(doc: &Doc, example: &Example) -> Option<String> {
+    let expected = example.expected.as_ref()?;
+    Some(format!(
+        "{}let\n  actual = {} (\n{}\n  );\n  expected = (\n{}\n  );\nin\nif actual == expected then {} else actual\n",
+        PRELUDE,
+        scope(doc),
+        indent(&example.expression, 4),
+        indent(expected, 4),
+        PASS
+    ))
+}
+
+/// A `lib.debug.runTests` file with one test per example that states a result.
+pub fn render_nix_tests(docs: &[(&Doc, Vec<Example>)]) -> String {
+    let mut out = format!("{}lib.debug.runTests {{\n", PRELUDE);
+
+    for (doc, examples) in docs {
+        for example in examples {
+            let Some(expected) = &example.expected else {
+                continue;
+            };
+            let _ = writeln!(
+                out,
+                "  \"test {} #{}\" = {{\n    expr = {} (\n{}\n    );\n    expected = (\n{}\n    );\n  }};",
+                example.function,
+                example.index,
+                scope(doc),
+                indent(&example.expression, 6),
+                indent(expected, 6)
+            );
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn indent(text: &str, width: usize) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", " ".repeat(width), line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(expression: &str, expected: Option<&str>) -> (String, Option<String>) {
+        (expression.to_string(), expected.map(str::to_string))
+    }
+
+    #[test]
+    fn multi_line_result() {
+        let code = "mapAttrs (n: v: v + 1) { a = 1; }\n=> {\n  a = 2;\n}\n";
+        assert_eq!(
+            split_pairs(code),
+            vec![pair(
+                "mapAttrs (n: v: v + 1) { a = 1; }",
+                Some("{\n  a = 2;\n}")
+            )]
+        );
+    }
+
+    #[test]
+    fn several_pairs_in_one_block() {
+        let code = "add 1 2\n=> 3\n\nadd 2\n  2\n=> 4\n";
+        assert_eq!(
+            split_pairs(code),
+            vec![pair("add 1 2", Some("3")), pair("add 2\n  2", Some("4"))]
+        );
+    }
+
+    #[test]
+    fn consecutive_results_without_blank_lines() {
+        let code = "head [ 1 ]\n=> 1\ntail [ 1 ]\n=> [ ]\n";
+        assert_eq!(
+            split_pairs(code),
+            vec![
+                pair("head [ 1 ]", Some("1")),
+                pair("tail [ 1 ]", Some("[ ]"))
+            ]
+        );
+    }
+
+    #[test]
+    fn block_without_result() {
+        assert_eq!(
+            split_pairs("\nfoo {\n  bar = 1;\n}\n\n"),
+            vec![pair("foo {\n  bar = 1;\n}", None)]
+        );
+        assert!(split_pairs("\n  \n").is_empty());
+    }
+
+    #[test]
+    fn brackets_inside_strings() {
+        let code = "splitString \"{\" \"a{b\"\n=> [ \"a\" \"b\" ]\nescape \"[\"\n=> \"\\\"[\"\n";
+        assert_eq!(
+            split_pairs(code),
+            vec![
+                pair("splitString \"{\" \"a{b\"", Some("[ \"a\" \"b\" ]")),
+                pair("escape \"[\"", Some("\"\\\"[\"")),
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_reads_only_the_examples_section() {
+        let doc: Doc = serde_json::from_value(serde_json::json!({
+            "meta": { "title": "lib.id", "path": ["lib", "id"], "is_primop": false },
+            "content": {
+                "content": "```nix\nignored 1\n=> 1\n```\n\n# Examples\n\n```nix\nid 1\n=> 1\n```\n\n```console\n$ nix repl\n```\n\n# Notes\n\n```nix\nignored 2\n```\n",
+            },
+        }))
+        .unwrap();
+
+        let examples = extract(&doc);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].function, "lib.id");
+        assert_eq!(examples[0].index, 1);
+        assert_eq!(examples[0].expression, "id 1");
+        assert_eq!(examples[0].expected.as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn balanced_ignores_string_contents() {
+        assert!(balanced("[ \"(\" \"]\" ]"));
+        assert!(balanced("\"\\\"{\""));
+        assert!(!balanced("{ a = \"}\";"));
+        assert!(!balanced("\"unterminated"));
+    }
+}
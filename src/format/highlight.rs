@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::html::escape;
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+    "let", "in", "if", "then", "else", "with", "inherit", "rec", "assert", "or",
+];
+const CONSTANTS: &[&str] = &["true", "false", "null"];
+const OPERATORS: &[&str] = &[
+    "=>", "->", "::", "++", "//", "==", "!=", "<=", ">=", "&&", "||", "?",
+];
+
+/// Wraps Nix tokens in `<span class="tok-…">` elements; the result is already HTML-escaped.
+pub fn highlight_nix(code: &str) -> String {
+    let chars: Vec<char> = code.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        let class = if c == '#' {
+            i = scan_until(&chars, i, "\n");
+            "comment"
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i = (scan_until(&chars, i + 2, "*/") + 2).min(chars.len());
+            "comment"
+        } else if c == '"' {
+            i = scan_string(&chars, i + 1, "\"");
+            "string"
+        } else if c == '\'' && chars.get(i + 1) == Some(&'\'') {
+            i = scan_string(&chars, i + 2, "''");
+            "string"
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            "number"
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && is_ident(chars[i]) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                "keyword"
+            } else if CONSTANTS.contains(&word.as_str()) {
+                "constant"
+            } else if c.is_uppercase() {
+                "type"
+            } else {
+                ""
+            }
+        } else if let Some(op) = OPERATORS
+            .iter()
+            .find(|op| chars[i..].starts_with(&op.chars().collect::<Vec<_>>()))
+        {
+            i += op.chars().count();
+            "operator"
+        } else {
+            i += 1;
+            ""
+        };
+
+        let token: String = chars[start..i].iter().collect();
+        if class.is_empty() {
+            out.push_str(&escape(&token));
+        } else {
+            let _ = write!(
+                out,
+                "<span class=\"tok-{}\">{}</span>",
+                class,
+                escape(&token)
+            );
+        }
+    }
+
+    out
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '\'')
+}
+
+/// Index of the first occurrence of `end` at or after `from`, or the end of input.
+fn scan_until(chars: &[char], from: usize, end: &str) -> usize {
+    let end: Vec<char> = end.chars().collect();
+    (from..chars.len())
+        .find(|&i| chars[i..].starts_with(&end))
+        .unwrap_or(chars.len())
+}
+
+/// Index just past the closing delimiter of a string, honouring backslash escapes.
+fn scan_string(chars: &[char], from: usize, end: &str) -> usize {
+    let end: Vec<char> = end.chars().collect();
+    let mut i = from;
+    while i < chars.len() {
+        if end.len() == 1 && chars[i] == '\\' {
+            i = (i + 2).min(chars.len());
+        } else if chars[i..].starts_with(&end) {
+            return i + end.len();
+        } else {
+            i += 1;
+        }
+    }
+    chars.len()
+}
@@ -0,0 +1,483 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::highlight::highlight_nix;
+use super::markdown::{Block, parse_blocks};
+use crate::data::{Doc, NoogleResponse, OptionMeta, OptionValue};
+use crate::urls::{self, SourceUrls};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+pub const STYLESHEET: &str = include_str!("style.css");
+
+/// Path of a function's page relative to the root of an exported site.
+pub fn doc_href(title: &str) -> String {
+    format!("functions/{}.html", file_stem(title))
+}
+
+/// Path of a namespace's page relative to the root of an exported site.
+pub fn namespace_href(namespace: &str) -> String {
+    format!("namespaces/{}.html", file_stem(namespace))
+}
+
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Cross-linking context for the pages of an exported site.
+pub struct Links<'a> {
+    /// Relative path from the page being rendered to the site root.
+    pub root: &'a str,
+    titles: HashSet<&'a str>,
+    aliased_by: HashMap<String, Vec<&'a str>>,
+}
+
+impl<'a> Links<'a> {
+    pub fn new(response: &'a NoogleResponse, root: &'a str) -> Self {
+        let mut aliased_by: HashMap<String, Vec<&'a str>> = HashMap::new();
+        for doc in &response.data {
+            for alias in doc.all_names().into_iter().skip(1) {
+                aliased_by.entry(alias).or_default().push(&doc.meta.title);
+            }
+        }
+
+        Links {
+            root,
+            titles: response
+                .data
+                .iter()
+                .map(|d| d.meta.title.as_str())
+                .collect(),
+            aliased_by,
+        }
+    }
+
+    /// A link to `name`'s page, or a plain code span when it has none.
+    fn name(&self, name: &str) -> String {
+        if self.titles.contains(name) {
+            format!(
+                "<a href=\"{}{}\"><code>{}</code></a>",
+                self.root,
+                escape(&doc_href(name)),
+                escape(name)
+            )
+        } else {
+            format!("<code>{}</code>", escape(name))
+        }
+    }
+}
+
+/// Wraps a fragment in a standalone document; `root` is the relative path to the site root.
+pub fn page(title: &str, body: &str, root: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<link rel=\"stylesheet\" href=\"{}style.css\">\n</head>\n\
+         <body>\n{}</body>\n</html>\n",
+        escape(title),
+        root,
+        body
+    )
+}
+
+/// Renders a namespace overview linking to its functions and sub-namespaces.
+pub fn render_namespace(namespace: &str, docs: &[&Doc], children: &[String], root: &str) -> String {
+    let mut out = String::new();
+
+    out.push_str("<article class=\"noogle-namespace\">\n");
+    let _ = writeln!(out, "<h1>{}</h1>", escape(namespace));
+
+    if !children.is_empty() {
+        out.push_str("<h2>Namespaces</h2>\n<ul class=\"namespaces\">\n");
+        for child in children {
+            let _ = writeln!(
+                out,
+                "<li><a href=\"{}{}\">{}</a></li>",
+                root,
+                escape(&namespace_href(child)),
+                escape(child)
+            );
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if !docs.is_empty() {
+        out.push_str("<h2>Functions</h2>\n<dl class=\"functions\">\n");
+        for doc in docs {
+            let summary = doc
+                .content
+                .as_ref()
+                .and_then(|c| c.content.as_deref())
+                .and_then(super::markdown::summary)
+                .map(|s| inline(&s))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "<dt><a href=\"{}{}\">{}</a></dt><dd>{}</dd>",
+                root,
+                escape(&doc_href(&doc.meta.title)),
+                escape(&doc.meta.title),
+                summary
+            );
+        }
+        out.push_str("</dl>\n");
+    }
+
+    out.push_str("</article>\n");
+
+    out
+}
+
+/// Renders a doc as an HTML fragment wrapped in `<article class="noogle-doc">`.
+///
+/// With `links`, aliases point at the pages of the functions they name and the
+/// functions listing this one as an alias are shown too.
+pub fn render_doc(doc: &Doc, source_urls: &SourceUrls, links: Option<&Links>) -> String {
+    let mut out = String::new();
+
+    out.push_str("<article class=\"noogle-doc\">\n");
+    let _ = writeln!(out, "<h1>{}</h1>", escape(&doc.meta.title));
+
+    if let Some(option) = &doc.meta.option {
+        out.push_str(&render_option(option));
+    }
+
+    if let Some(sig) = &doc.meta.signature {
+        let _ = writeln!(
+            out,
+            "<pre class=\"signature\"><code class=\"language-nix\">{}</code></pre>",
+            highlight_nix(sig.trim())
+        );
+    }
+
+    if let Some(text) = doc.content.as_ref().and_then(|c| c.content.as_ref()) {
+        out.push_str(&render_blocks(&parse_blocks(text), 1));
+    }
+
+    if let Some(position) = urls::source_position(doc) {
+        let file_short = position
+            .file
+            .split('/')
+            .next_back()
+            .unwrap_or(&position.file);
+        let location = escape(&format!("{}:{}", file_short, position.line));
+        match source_urls.url(doc) {
+            Some(url) => {
+                let _ = writeln!(
+                    out,
+                    "<p class=\"source\">Source: <a href=\"{}\">{}</a></p>",
+                    escape(&url),
+                    location
+                );
+            }
+            None => {
+                let _ = writeln!(out, "<p class=\"source\">Source: {}</p>", location);
+            }
+        }
+    }
+
+    if let Some(aliases) = doc.meta.aliases.as_ref().filter(|a| !a.is_empty()) {
+        out.push_str("<h2>Aliases</h2>\n<ul class=\"aliases\">\n");
+        for alias in aliases {
+            let name = alias.join(".");
+            let item = match links {
+                Some(links) => links.name(&name),
+                None => format!("<code>{}</code>", escape(&name)),
+            };
+            let _ = writeln!(out, "<li>{}</li>", item);
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if let Some(links) = links
+        && let Some(titles) = links.aliased_by.get(&doc.meta.title)
+    {
+        out.push_str("<h2>Aliased by</h2>\n<ul class=\"aliases\">\n");
+        for title in titles {
+            let _ = writeln!(out, "<li>{}</li>", links.name(title));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if let Some(url) = source_urls.noogle_url(doc) {
+        let _ = writeln!(
+            out,
+            "<p class=\"noogle\"><a href=\"{}\">View on Noogle</a></p>",
+            escape(&url)
+        );
+    }
+    out.push_str("</article>\n");
+
+    out
+}
+
+fn render_option(option: &OptionMeta) -> String {
+    let mut out = String::from("<dl class=\"option\">\n");
+
+    if let Some(ty) = &option.option_type {
+        let _ = writeln!(out, "<dt>Type</dt><dd>{}</dd>", escape(ty));
+    }
+    if option.read_only {
+        out.push_str("<dt>Read-only</dt><dd>yes</dd>\n");
+    }
+    for (label, value) in [("Default", &option.default), ("Example", &option.example)] {
+        match value {
+            Some(OptionValue::Nix(code)) => {
+                let _ = writeln!(
+                    out,
+                    "<dt>{}</dt><dd><pre><code class=\"language-nix\">{}</code></pre></dd>",
+                    label,
+                    highlight_nix(code.trim())
+                );
+            }
+            Some(OptionValue::Markdown(text)) => {
+                let _ = writeln!(
+                    out,
+                    "<dt>{}</dt><dd>{}</dd>",
+                    label,
+                    render_blocks(&parse_blocks(text), 3).trim_end()
+                );
+            }
+            None => {}
+        }
+    }
+    if !option.declarations.is_empty() {
+        out.push_str("<dt>Declared in</dt>\n");
+        for declaration in &option.declarations {
+            let name = format!("<code>{}</code>", escape(&declaration.name));
+            match &declaration.url {
+                Some(url) => {
+                    let _ = writeln!(out, "<dd><a href=\"{}\">{}</a></dd>", escape(url), name);
+                }
+                None => {
+                    let _ = writeln!(out, "<dd>{}</dd>", name);
+                }
+            }
+        }
+    }
+
+    out.push_str("</dl>\n");
+    out
+}
+
+/// Renders blocks to HTML, shifting headings down by `heading_offset` levels.
+pub fn render_blocks(blocks: &[Block], heading_offset: usize) -> String {
+    let mut out = String::new();
+    let mut in_definitions = false;
+
+    for block in blocks {
+        let is_definition = matches!(block, Block::Definition { .. });
+        if in_definitions && !is_definition {
+            out.push_str("</dl>\n");
+        } else if !in_definitions && is_definition {
+            out.push_str("<dl>\n");
+        }
+        in_definitions = is_definition;
+
+        match block {
+            Block::Heading { level, text } => {
+                let level = (level + heading_offset).min(6);
+                let _ = writeln!(out, "<h{0}>{1}</h{0}>", level, inline(text));
+            }
+            Block::Code { lang, code } => {
+                let lang = if lang.is_empty() { "nix" } else { lang };
+                let code = if lang == "nix" {
+                    highlight_nix(code)
+                } else {
+                    escape(code)
+                };
+                let _ = writeln!(
+                    out,
+                    "<pre><code class=\"language-{}\">{}</code></pre>",
+                    escape(lang),
+                    code
+                );
+            }
+            Block::Paragraph(text) => {
+                let _ = writeln!(out, "<p>{}</p>", inline(text));
+            }
+            Block::Definition { term, description } => {
+                let _ = writeln!(
+                    out,
+                    "<dt>{}</dt><dd>{}</dd>",
+                    inline(term),
+                    inline(description)
+                );
+            }
+            Block::List(items) => {
+                out.push_str("<ul>\n");
+                for item in items {
+                    let _ = writeln!(out, "<li>{}</li>", inline(item));
+                }
+                out.push_str("</ul>\n");
+            }
+        }
+    }
+
+    if in_definitions {
+        out.push_str("</dl>\n");
+    }
+
+    out
+}
+
+/// Converts inline markdown (code spans, emphasis, links, escapes) to HTML.
+pub fn inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() => {
+                out.push_str(&escape(&chars[i + 1].to_string()));
+                i += 2;
+            }
+            '`' => match find(&chars, i + 1, "`") {
+                Some(end) => {
+                    let code: String = chars[i + 1..end].iter().collect();
+                    let _ = write!(out, "<code>{}</code>", escape(&code));
+                    i = end + 1;
+                }
+                None => {
+                    out.push('`');
+                    i += 1;
+                }
+            },
+            '*' if chars.get(i + 1) == Some(&'*') => match find(&chars, i + 2, "**") {
+                Some(end) => {
+                    let inner: String = chars[i + 2..end].iter().collect();
+                    let _ = write!(out, "<strong>{}</strong>", inline(&inner));
+                    i = end + 2;
+                }
+                None => {
+                    out.push_str("**");
+                    i += 2;
+                }
+            },
+            '*' | '_' if chars.get(i + 1).is_some_and(|c| !c.is_whitespace()) => {
+                let marker = chars[i].to_string();
+                let at_word_start = i == 0 || !chars[i - 1].is_alphanumeric();
+                match find(&chars, i + 1, &marker).filter(|_| at_word_start) {
+                    Some(end) => {
+                        let inner: String = chars[i + 1..end].iter().collect();
+                        let _ = write!(out, "<em>{}</em>", inline(&inner));
+                        i = end + 1;
+                    }
+                    None => {
+                        out.push(chars[i]);
+                        i += 1;
+                    }
+                }
+            }
+            '[' => match parse_link(&chars, i) {
+                Some((label, href, end)) if is_safe_href(&href) => {
+                    let _ = write!(out, "<a href=\"{}\">{}</a>", escape(&href), inline(&label));
+                    i = end;
+                }
+                Some((label, _, end)) => {
+                    out.push_str(&inline(&label));
+                    i = end;
+                }
+                None => {
+                    out.push('[');
+                    i += 1;
+                }
+            },
+            c => {
+                out.push_str(&escape(&c.to_string()));
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+fn find(chars: &[char], from: usize, needle: &str) -> Option<usize> {
+    let needle: Vec<char> = needle.chars().collect();
+    (from..chars.len()).find(|&i| chars[i..].starts_with(&needle))
+}
+
+fn parse_link(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    let close = find(chars, start + 1, "](")?;
+    let end = find(chars, close + 2, ")")?;
+    let label = chars[start + 1..close].iter().collect();
+    let href = chars[close + 2..end].iter().collect();
+    Some((label, href, end + 1))
+}
+
+/// Whether a link target is relative or uses http(s), so doc text can't inject
+/// `javascript:` and similar URLs.
+fn is_safe_href(href: &str) -> bool {
+    match href.find([':', '/', '?', '#']) {
+        Some(i) if href[i..].starts_with(':') => {
+            // Browsers ignore whitespace and control characters inside the scheme.
+            let scheme: String = href[..i]
+                .chars()
+                .filter(|c| !c.is_whitespace() && !c.is_control())
+                .collect();
+            scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+        }
+        _ => true,
+    }
+}
+
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_keep_http_and_relative_targets() {
+        assert_eq!(
+            inline("[Nix](https://nixos.org/?a=1&b=2)"),
+            "<a href=\"https://nixos.org/?a=1&amp;b=2\">Nix</a>"
+        );
+        assert_eq!(
+            inline("[up](../index.html#top)"),
+            "<a href=\"../index.html#top\">up</a>"
+        );
+        assert_eq!(inline("[q](?x=a:b)"), "<a href=\"?x=a:b\">q</a>");
+    }
+
+    #[test]
+    fn links_with_other_schemes_become_text() {
+        assert_eq!(inline("[click](javascript:alert(1))"), "click)");
+        assert_eq!(inline("[**x**](JavaScript:alert)"), "<strong>x</strong>");
+        assert_eq!(inline("[x](java\tscript:alert)"), "x");
+        assert_eq!(inline("[x]( data:text/html,hi)"), "x");
+    }
+}
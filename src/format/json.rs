@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::Doc;
+use crate::urls::{self, SourceUrls};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct JsonDoc<'a> {
+    #[serde(flatten)]
+    pub doc: &'a Doc,
+    pub urls: JsonUrls,
+}
+
+#[derive(Serialize)]
+pub struct JsonUrls {
+    pub noogle: String,
+    pub source: Option<String>,
+}
+
+/// The full doc plus its resolved noogle and source URLs.
+pub fn render_doc<'a>(doc: &'a Doc, source_urls: &SourceUrls) -> JsonDoc<'a> {
+    JsonDoc {
+        doc,
+        urls: JsonUrls {
+            noogle: urls::noogle_url(doc),
+            source: source_urls.url(doc),
+        },
+    }
+}
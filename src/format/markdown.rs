@@ -0,0 +1,258 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Doc, OptionValue};
+use crate::urls::{self, SourceUrls};
+use std::fmt::Write;
+
+/// A block-level element of a Noogle doc comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Heading { level: usize, text: String },
+    Code { lang: String, code: String },
+    Paragraph(String),
+    Definition { term: String, description: String },
+    List(Vec<String>),
+}
+
+/// Splits doc comment markdown into blocks, dropping `:::` admonition fences.
+pub fn parse_blocks(text: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<(String, String)> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if let Some((lang, buffer)) = &mut code {
+            if trimmed == "```" {
+                blocks.push(Block::Code {
+                    lang: std::mem::take(lang),
+                    code: buffer.trim_end().to_string(),
+                });
+                code = None;
+            } else {
+                buffer.push_str(line);
+                buffer.push('\n');
+            }
+            continue;
+        }
+
+        if let Some(lang) = trimmed.strip_prefix("```") {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            code = Some((lang.trim().to_string(), String::new()));
+        } else if trimmed.starts_with(":::") || trimmed.is_empty() {
+            flush_paragraph(&mut paragraph, &mut blocks);
+        } else if let Some((level, heading)) = parse_heading(trimmed) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading {
+                level,
+                text: heading.to_string(),
+            });
+        } else if let Some(description) = trimmed.strip_prefix(": ") {
+            let term = paragraph.join(" ");
+            paragraph.clear();
+            blocks.push(Block::Definition {
+                term,
+                description: description.to_string(),
+            });
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            match blocks.last_mut() {
+                Some(Block::List(items)) => items.push(item.to_string()),
+                _ => blocks.push(Block::List(vec![item.to_string()])),
+            }
+        } else if line.starts_with(char::is_whitespace) && paragraph.is_empty() {
+            match blocks.last_mut() {
+                Some(Block::Definition { description, .. }) => {
+                    description.push(' ');
+                    description.push_str(trimmed);
+                }
+                Some(Block::List(items)) => {
+                    let last = items.last_mut().expect("lists are never empty");
+                    last.push(' ');
+                    last.push_str(trimmed);
+                }
+                _ => paragraph.push(trimmed),
+            }
+        } else {
+            paragraph.push(line.trim_end());
+        }
+    }
+
+    if let Some((lang, buffer)) = code {
+        blocks.push(Block::Code {
+            lang,
+            code: buffer.trim_end().to_string(),
+        });
+    }
+    flush_paragraph(&mut paragraph, &mut blocks);
+
+    blocks
+}
+
+fn flush_paragraph(paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+    if !paragraph.is_empty() {
+        blocks.push(Block::Paragraph(paragraph.join("\n")));
+        paragraph.clear();
+    }
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) {
+        line[level..]
+            .strip_prefix(' ')
+            .map(|rest| (level, rest.trim()))
+    } else {
+        None
+    }
+}
+
+/// The first sentence of the first paragraph, with inline markup left intact.
+pub fn summary(text: &str) -> Option<String> {
+    let first = parse_blocks(text)
+        .into_iter()
+        .find_map(|block| match block {
+            Block::Paragraph(p) => Some(p),
+            _ => None,
+        })?;
+    let flat = first.split_whitespace().collect::<Vec<_>>().join(" ");
+    match flat.find(". ") {
+        Some(end) => Some(flat[..=end].to_string()),
+        None => Some(flat),
+    }
+}
+
+/// Renders a doc as standalone markdown, with doc comment headings nested below the title.
+pub fn render_doc(doc: &Doc, source_urls: &SourceUrls) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# {}\n", doc.meta.title);
+
+    if let Some(option) = &doc.meta.option {
+        if let Some(ty) = &option.option_type {
+            let _ = writeln!(out, "Type: {}\n", ty);
+        }
+        if option.read_only {
+            out.push_str("Read-only.\n\n");
+        }
+        for (label, value) in [("Default", &option.default), ("Example", &option.example)] {
+            match value {
+                Some(OptionValue::Nix(code)) => {
+                    let _ = writeln!(out, "{}:\n\n```nix\n{}\n```\n", label, code.trim());
+                }
+                Some(OptionValue::Markdown(text)) => {
+                    let _ = writeln!(out, "{}:\n\n{}\n", label, text.trim());
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let Some(sig) = &doc.meta.signature {
+        let _ = writeln!(out, "```nix\n{}\n```\n", sig.trim());
+    }
+
+    if let Some(text) = doc.content.as_ref().and_then(|c| c.content.as_ref()) {
+        out.push_str(&render_blocks(&parse_blocks(text), 1));
+    }
+
+    if let Some(position) = urls::source_position(doc) {
+        let file_short = position
+            .file
+            .split('/')
+            .next_back()
+            .unwrap_or(&position.file);
+        let location = format!("{}:{}", file_short, position.line);
+        match source_urls.url(doc) {
+            Some(url) => {
+                let _ = writeln!(out, "Source: [{}]({})\n", location, url);
+            }
+            None => {
+                let _ = writeln!(out, "Source: `{}`\n", location);
+            }
+        }
+    }
+
+    if let Some(option) = doc
+        .meta
+        .option
+        .as_ref()
+        .filter(|o| !o.declarations.is_empty())
+    {
+        out.push_str("## Declared in\n\n");
+        for declaration in &option.declarations {
+            match &declaration.url {
+                Some(url) => {
+                    let _ = writeln!(out, "- [{}]({})", declaration.name, url);
+                }
+                None => {
+                    let _ = writeln!(out, "- `{}`", declaration.name);
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    if let Some(aliases) = doc.meta.aliases.as_ref().filter(|a| !a.is_empty()) {
+        out.push_str("## Aliases\n\n");
+        for alias in aliases {
+            let _ = writeln!(out, "- `{}`", alias.join("."));
+        }
+        out.push('\n');
+    }
+
+    if let Some(url) = source_urls.noogle_url(doc) {
+        let _ = writeln!(out, "[View on Noogle]({})", url);
+    }
+
+    out
+}
+
+/// Renders blocks back to markdown, shifting headings down by `heading_offset` levels.
+pub fn render_blocks(blocks: &[Block], heading_offset: usize) -> String {
+    let mut out = String::new();
+
+    for block in blocks {
+        match block {
+            Block::Heading { level, text } => {
+                let level = (level + heading_offset).min(6);
+                let _ = writeln!(out, "{} {}\n", "#".repeat(level), text);
+            }
+            Block::Code { lang, code } => {
+                let _ = writeln!(out, "```{}\n{}\n```\n", lang, code);
+            }
+            Block::Paragraph(text) => {
+                let _ = writeln!(out, "{}\n", text);
+            }
+            Block::Definition { term, description } => {
+                let _ = writeln!(out, "{}\n: {}\n", term, description);
+            }
+            Block::List(items) => {
+                for item in items {
+                    let _ = writeln!(out, "- {}", item);
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    out
+}
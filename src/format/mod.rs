@@ -0,0 +1,437 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+pub mod highlight;
+pub mod html;
+pub mod json;
+pub mod markdown;
+pub mod roff;
+
+use crate::data::{BuiltinType, Doc, OptionMeta, OptionValue};
+use crate::index::Index;
+use crate::related::{self, SeeAlso};
+use crate::urls::SourceUrls;
+use owo_colors::{OwoColorize, Style};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+fn paint(style: Style, color: bool) -> Style {
+    if color { style } else { Style::new() }
+}
+
+fn title_style(color: bool) -> Style {
+    paint(Style::new().bright_cyan().bold(), color)
+}
+
+fn heading_style(color: bool) -> Style {
+    paint(Style::new().yellow().bold(), color)
+}
+
+fn subheading_style(color: bool) -> Style {
+    paint(Style::new().yellow(), color)
+}
+
+fn dim_style(color: bool) -> Style {
+    paint(Style::new().dimmed(), color)
+}
+
+fn file_style(color: bool) -> Style {
+    paint(Style::new().blue(), color)
+}
+
+fn reference_style(color: bool) -> Style {
+    paint(Style::new().green().underline(), color)
+}
+
+/// Renders the terminal preview, with escape codes only if `color` is set.
+///
+/// Builtin types named in the signature are described below it, and functions
+/// named in the text are highlighted and linked to noogle.dev.
+pub fn render_preview(
+    doc: &Doc,
+    types: &BTreeMap<String, BuiltinType>,
+    index: &Index,
+    color: bool,
+) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "{}\n", doc.meta.title.style(title_style(color)));
+
+    if let Some(option) = &doc.meta.option {
+        render_option(option, color, &mut out);
+    }
+
+    if let Some(sig) = &doc.meta.signature {
+        let _ = writeln!(out, "{}", "Type Signature:".style(heading_style(color)));
+        out.push_str(&highlight_code(sig.trim(), "nix", color));
+        out.push_str("\n\n");
+
+        let sig_types = doc.signature_types(types);
+        if !sig_types.is_empty() {
+            let _ = writeln!(out, "{}", "Types:".style(heading_style(color)));
+            for (key, ty) in sig_types {
+                let label = if ty.name == key {
+                    format!("{}:", key)
+                } else {
+                    format!("{} ({}):", key, ty.name)
+                };
+                let _ = writeln!(
+                    out,
+                    "  {} {}",
+                    label.style(dim_style(color)),
+                    strip_inline_code(&ty.description)
+                );
+            }
+            out.push('\n');
+        }
+    }
+
+    if let Some(primop) = &doc.meta.primop_meta {
+        let _ = writeln!(out, "{}", "Primop:".style(heading_style(color)));
+        if let Some(arity) = primop.arity {
+            let _ = writeln!(out, "  {} {}", "arity:".style(dim_style(color)), arity);
+        }
+        if !primop.args.is_empty() {
+            let _ = writeln!(
+                out,
+                "  {} {}",
+                "args:".style(dim_style(color)),
+                primop.args.join(" ")
+            );
+        }
+        if primop.experimental {
+            let feature = primop.experimental_feature.as_deref().unwrap_or("yes");
+            let _ = writeln!(
+                out,
+                "  {} {}",
+                "experimental feature:".style(dim_style(color)),
+                feature
+            );
+        }
+        out.push('\n');
+    }
+
+    if let Some(lambda) = &doc.meta.lambda_expr {
+        let _ = writeln!(out, "{}", "Definition:".style(heading_style(color)));
+        out.push_str(&highlight_code(lambda, "nix", color));
+    }
+
+    if let Some(text) = doc.content.as_ref().and_then(|c| c.content.as_ref()) {
+        render_content(
+            text,
+            &|line| link_references(line, doc, index, color),
+            color,
+            &mut out,
+        );
+    }
+
+    if let Some(pos) = &doc.meta.lambda_position {
+        let file_short = pos.file.split('/').next_back().unwrap_or(&pos.file);
+        let _ = writeln!(
+            out,
+            "\n{} {}:{}:{}",
+            "Source:".style(dim_style(color)),
+            file_short.style(file_style(color)),
+            pos.line,
+            pos.column
+        );
+    }
+
+    if let Some(aliases) = doc.meta.aliases.as_ref().filter(|a| !a.is_empty()) {
+        let _ = writeln!(out, "\n{}", "Aliases:".style(heading_style(color)));
+        for alias in aliases {
+            let _ = writeln!(out, "  {}", alias.join(".").style(dim_style(color)));
+        }
+    }
+
+    out
+}
+
+/// Renders the "See also" section listing related functions, or nothing if there are none.
+pub fn render_see_also(see_also: &SeeAlso, color: bool) -> String {
+    if see_also.is_empty() {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "\n{}", "See also:".style(heading_style(color)));
+    for (label, titles) in [
+        ("aliases:", &see_also.aliases),
+        ("mentioned:", &see_also.mentions),
+        ("similar:", &see_also.similar),
+        ("namespace:", &see_also.siblings),
+    ] {
+        if !titles.is_empty() {
+            let _ = writeln!(
+                out,
+                "  {:<11}{}",
+                label.style(dim_style(color)),
+                titles.join(", ")
+            );
+        }
+    }
+    out
+}
+
+/// Renders `context` lines either side of `line` in `path`, numbered and highlighted.
+pub fn render_source(path: &Path, line: u32, context: usize, color: bool) -> String {
+    let Ok(content) = fs::read_to_string(path) else {
+        return String::new();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let line = line as usize;
+    let first = line.saturating_sub(context).max(1);
+    let last = (line + context).min(lines.len());
+    if first > last {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "\n{} {}",
+        "Source code:".style(heading_style(color)),
+        path.display().style(file_style(color))
+    );
+
+    if color
+        && let Ok(output) = Command::new("bat")
+            .args([
+                "--color=always",
+                "--style=numbers",
+                "--language=nix",
+                &format!("--line-range={}:{}", first, last),
+                &format!("--highlight-line={}", line),
+            ])
+            .arg(path)
+            .output()
+        && output.status.success()
+    {
+        out.push_str(&String::from_utf8_lossy(&output.stdout));
+        return out;
+    }
+
+    let width = last.to_string().len();
+    for (number, text) in (first..=last).zip(&lines[first - 1..last]) {
+        let marker = if number == line { ">" } else { " " };
+        let _ = writeln!(
+            out,
+            "{}{:>width$} {}",
+            marker,
+            number.style(dim_style(color)),
+            text,
+            width = width
+        );
+    }
+
+    out
+}
+
+fn render_option(option: &OptionMeta, color: bool, out: &mut String) {
+    if let Some(ty) = &option.option_type {
+        let _ = writeln!(out, "{} {}", "Type:".style(heading_style(color)), ty);
+    }
+    if option.read_only {
+        let _ = writeln!(out, "{}", "Read-only".style(dim_style(color)));
+    }
+    out.push('\n');
+
+    for (label, value) in [("Default:", &option.default), ("Example:", &option.example)] {
+        let Some(value) = value else {
+            continue;
+        };
+        let _ = writeln!(out, "{}", label.style(heading_style(color)));
+        match value {
+            OptionValue::Nix(code) => {
+                out.push_str(&highlight_code(code.trim(), "nix", color));
+                out.push_str("\n\n");
+            }
+            OptionValue::Markdown(text) => {
+                render_content(text.trim(), &strip_inline_code, color, out);
+                out.push('\n');
+            }
+        }
+    }
+
+    if !option.declarations.is_empty() {
+        let _ = writeln!(out, "{}", "Declared in:".style(heading_style(color)));
+        for declaration in &option.declarations {
+            let _ = writeln!(out, "  {}", declaration.name.style(file_style(color)));
+            if let Some(url) = &declaration.url {
+                let _ = writeln!(out, "  {}", url.style(dim_style(color)));
+            }
+        }
+        out.push('\n');
+    }
+}
+
+fn highlight_code(code: &str, lang: &str, color: bool) -> String {
+    if !color {
+        return code.to_string();
+    }
+
+    if let Ok(mut child) = Command::new("bat")
+        .args([
+            "--color=always",
+            "--style=plain",
+            &format!("--language={}", lang),
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+    {
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(code.as_bytes());
+        }
+        if let Ok(output) = child.wait_with_output() {
+            return String::from_utf8_lossy(&output.stdout).into_owned();
+        }
+    }
+    code.to_string()
+}
+
+fn render_content(text: &str, inline: &dyn Fn(&str) -> String, color: bool, out: &mut String) {
+    let mut in_code_block = false;
+    let mut code_buffer = String::new();
+    let mut last_was_empty = false;
+    let mut last_was_heading = false;
+    let mut skip_next_usage_line = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```nix") || trimmed.starts_with("```") && trimmed.len() == 3 {
+            if !in_code_block {
+                in_code_block = true;
+                code_buffer.clear();
+            } else {
+                in_code_block = false;
+                if !code_buffer.is_empty() {
+                    let trimmed_code = code_buffer.trim();
+                    out.push_str(&highlight_code(trimmed_code, "nix", color));
+                    out.push('\n');
+                }
+            }
+            last_was_heading = false;
+            skip_next_usage_line = false;
+        } else if in_code_block {
+            code_buffer.push_str(line);
+            code_buffer.push('\n');
+        } else if trimmed.starts_with(":::") {
+            continue;
+        } else if trimmed.is_empty() {
+            if !last_was_empty && !last_was_heading {
+                out.push('\n');
+                last_was_empty = true;
+            }
+            last_was_heading = false;
+        } else if skip_next_usage_line && trimmed.contains("usage example") {
+            skip_next_usage_line = false;
+            continue;
+        } else if trimmed.starts_with(": ") {
+            let content = trimmed.trim_start_matches(": ");
+            let _ = writeln!(out, "  - {}", inline(content));
+            last_was_empty = false;
+            last_was_heading = false;
+            skip_next_usage_line = false;
+        } else if trimmed.starts_with("# ") {
+            let heading = trimmed.trim_start_matches("# ");
+            let _ = writeln!(
+                out,
+                "{}",
+                strip_inline_code(heading).style(heading_style(color))
+            );
+            last_was_empty = false;
+            last_was_heading = true;
+            skip_next_usage_line = heading == "Examples";
+        } else if trimmed.starts_with("## ") {
+            let heading = trimmed.trim_start_matches("## ");
+            let _ = writeln!(
+                out,
+                "{}",
+                strip_inline_code(heading).style(subheading_style(color))
+            );
+            last_was_empty = false;
+            last_was_heading = true;
+            skip_next_usage_line = heading == "Examples";
+        } else {
+            let _ = writeln!(out, "{}", inline(line));
+            last_was_empty = false;
+            last_was_heading = false;
+            skip_next_usage_line = false;
+        }
+    }
+}
+
+/// Like [`strip_inline_code`], but code spans naming a known function are
+/// highlighted and, with `color` set, wrapped in an OSC 8 link to noogle.dev, or to
+/// the source of functions from merged sources.
+fn link_references(text: &str, doc: &Doc, index: &Index, color: bool) -> String {
+    let source_urls = SourceUrls::new(index.response());
+    let mut out = String::new();
+    for (i, part) in text.split('`').enumerate() {
+        let target = (i % 2 == 1 && related::is_name(part.trim()))
+            .then(|| index.resolve(part.trim(), &doc.namespace()))
+            .flatten();
+        match target {
+            Some(target) => {
+                let name = part.trim().style(reference_style(color)).to_string();
+                let url = source_urls
+                    .noogle_url(target)
+                    .or_else(|| source_urls.url(target));
+                match url {
+                    Some(url) => out.push_str(&hyperlink(&url, &name, color)),
+                    None => out.push_str(&name),
+                }
+            }
+            None => out.push_str(&unescape_markdown(part)),
+        }
+    }
+    out
+}
+
+/// Wraps `text` in an OSC 8 terminal hyperlink when `color` is set.
+pub fn hyperlink(url: &str, text: &str, color: bool) -> String {
+    if color {
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn strip_inline_code(text: &str) -> String {
+    unescape_markdown(&text.replace('`', ""))
+}
+
+fn unescape_markdown(text: &str) -> String {
+    text.replace(r"\.", ".")
+        .replace(r"\-", "-")
+        .replace(r"\*", "*")
+        .replace(r"\_", "_")
+        .replace(r"\#", "#")
+        .replace(r"\[", "[")
+        .replace(r"\]", "]")
+        .replace(r"\(", "(")
+        .replace(r"\)", ")")
+        .replace(r"\{", "{")
+        .replace(r"\}", "}")
+        .replace(r"\|", "|")
+        .replace(r"\\", "\\")
+}
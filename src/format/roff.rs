@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::markdown::{Block, parse_blocks};
+use crate::data::{Doc, RepoInfo};
+use crate::urls::{self, SourceUrls};
+use chrono::DateTime;
+use std::fmt::Write;
+
+pub const SECTION: &str = "3";
+
+/// Renders a function's man page.
+pub fn render_doc(doc: &Doc, upstream: &RepoInfo, source_urls: &SourceUrls) -> String {
+    let mut out = header(&doc.meta.title, upstream);
+    let text = doc.content.as_ref().and_then(|c| c.content.as_deref());
+
+    out.push_str(".SH NAME\n");
+    let _ = writeln!(
+        out,
+        "{} \\- {}",
+        escape(&doc.meta.title),
+        escape(&summary(text).unwrap_or_else(|| "Nix function".to_string()))
+    );
+
+    if let Some(sig) = &doc.meta.signature {
+        out.push_str(".SH SYNOPSIS\n.nf\n");
+        for line in sig.trim().lines() {
+            let _ = writeln!(out, "{}", escape_line(line));
+        }
+        out.push_str(".fi\n");
+    }
+
+    if let Some(text) = text {
+        out.push_str(".SH DESCRIPTION\n");
+        out.push_str(&render_blocks(&parse_blocks(text)));
+    }
+
+    if let Some(aliases) = doc.meta.aliases.as_ref().filter(|a| !a.is_empty()) {
+        out.push_str(".SH ALIASES\n");
+        for alias in aliases {
+            let _ = writeln!(out, ".IP \\(bu 2\n\\fB{}\\fR", escape(&alias.join(".")));
+        }
+    }
+
+    if let Some(position) = urls::source_position(doc) {
+        out.push_str(".SH SOURCE\n");
+        let _ = writeln!(
+            out,
+            "{}:{}:{}",
+            escape(&position.file),
+            position.line,
+            position.column
+        );
+        if let Some(url) = source_urls.url(doc) {
+            let _ = writeln!(out, ".br\n{}", escape(&url));
+        }
+    }
+
+    out.push_str(".SH SEE ALSO\n");
+    let _ = writeln!(out, "{}", escape(&urls::noogle_url(doc)));
+
+    out
+}
+
+/// Renders a namespace overview listing its functions and sub-namespaces.
+pub fn render_namespace(
+    namespace: &str,
+    docs: &[&Doc],
+    children: &[String],
+    upstream: &RepoInfo,
+) -> String {
+    let mut out = header(namespace, upstream);
+
+    out.push_str(".SH NAME\n");
+    let _ = writeln!(
+        out,
+        "{} \\- Nix namespace with {} functions",
+        escape(namespace),
+        docs.len()
+    );
+
+    if !docs.is_empty() {
+        out.push_str(".SH FUNCTIONS\n");
+        for doc in docs {
+            let text = doc.content.as_ref().and_then(|c| c.content.as_deref());
+            let _ = writeln!(out, ".TP\n\\fB{}\\fR(3)", escape(&doc.meta.title));
+            match summary(text) {
+                Some(line) => {
+                    let _ = writeln!(out, "{}", escape(&line));
+                }
+                None => out.push_str("Undocumented.\n"),
+            }
+        }
+    }
+
+    if !children.is_empty() {
+        out.push_str(".SH NAMESPACES\n");
+        for child in children {
+            let _ = writeln!(out, ".IP \\(bu 2\n\\fB{}\\fR(3)", escape(child));
+        }
+    }
+
+    out
+}
+
+/// A `.so` page redirecting an alias to the page of the function it names.
+pub fn render_alias(target: &str) -> String {
+    format!(".so man{}/{}.{}\n", SECTION, target, SECTION)
+}
+
+fn header(name: &str, upstream: &RepoInfo) -> String {
+    let date = DateTime::from_timestamp(upstream.last_modified as i64, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let rev: String = upstream.rev.chars().take(12).collect();
+
+    format!(
+        ".TH \"{}\" \"{}\" \"{}\" \"nixpkgs {}\" \"Nix Library Functions\"\n",
+        escape(name),
+        SECTION,
+        date,
+        rev
+    )
+}
+
+fn summary(text: Option<&str>) -> Option<String> {
+    super::markdown::summary(text?).map(|s| strip_markup(&s))
+}
+
+fn render_blocks(blocks: &[Block]) -> String {
+    let mut out = String::new();
+
+    for block in blocks {
+        match block {
+            Block::Heading { level: 1, text } => {
+                let _ = writeln!(out, ".SS \"{}\"", escape(&strip_markup(text)));
+            }
+            Block::Heading { text, .. } => {
+                let _ = writeln!(out, ".PP\n\\fB{}\\fR", escape(&strip_markup(text)));
+            }
+            Block::Code { code, .. } => {
+                out.push_str(".PP\n.RS 4\n.nf\n");
+                for line in code.lines() {
+                    let _ = writeln!(out, "{}", escape_line(line));
+                }
+                out.push_str(".fi\n.RE\n");
+            }
+            Block::Paragraph(text) => {
+                let _ = writeln!(out, ".PP\n{}", inline(&text.replace('\n', " ")));
+            }
+            Block::Definition { term, description } => {
+                let _ = writeln!(out, ".TP\n{}\n{}", inline(term), inline(description));
+            }
+            Block::List(items) => {
+                for item in items {
+                    let _ = writeln!(out, ".IP \\(bu 2\n{}", inline(item));
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Converts inline markdown to roff font escapes.
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut in_code = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => {
+                out.push_str(if in_code { "\\fR" } else { "\\fB" });
+                in_code = !in_code;
+            }
+            '\\' if !in_code && chars.peek().is_some_and(|n| n.is_ascii_punctuation()) => {
+                let next = chars.next().unwrap_or_default();
+                out.push_str(&escape(&next.to_string()));
+            }
+            c => out.push_str(&escape(&c.to_string())),
+        }
+    }
+
+    if in_code {
+        out.push_str("\\fR");
+    }
+
+    escape_line_start(&out)
+}
+
+fn strip_markup(text: &str) -> String {
+    super::unescape_markdown(&text.replace('`', ""))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\e").replace('-', "\\-")
+}
+
+fn escape_line(line: &str) -> String {
+    escape_line_start(&escape(line))
+}
+
+/// Keeps lines starting with `.` or `'` from being read as requests.
+fn escape_line_start(line: &str) -> String {
+    if line.starts_with('.') || line.starts_with('\'') {
+        format!("\\&{}", line)
+    } else {
+        line.to_string()
+    }
+}
@@ -0,0 +1,360 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Doc, NoogleResponse};
+use std::collections::{BTreeMap, HashMap};
+
+/// Name-based lookups over a loaded response.
+pub struct Index<'a> {
+    response: &'a NoogleResponse,
+    by_name: HashMap<String, &'a Doc>,
+}
+
+/// Which parts of a doc a search query is matched against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Fuzzy matching on titles and aliases.
+    Fuzzy,
+    /// Every query word must appear in the title, signature or doc text.
+    Text,
+    /// Both, ranking name matches first.
+    #[default]
+    All,
+}
+
+pub struct SearchHit<'a> {
+    pub doc: &'a Doc,
+    pub score: u32,
+}
+
+/// An entry directly below a namespace.
+pub enum Child<'a> {
+    Namespace(String),
+    Doc { name: String, doc: &'a Doc },
+}
+
+impl<'a> Index<'a> {
+    pub fn new(response: &'a NoogleResponse) -> Self {
+        let mut by_name = HashMap::new();
+        for doc in &response.data {
+            for alias in doc.all_names().into_iter().skip(1) {
+                by_name.entry(alias).or_insert(doc);
+            }
+        }
+        // Titles win over aliases that happen to share a name.
+        for doc in &response.data {
+            by_name.insert(doc.meta.title.clone(), doc);
+        }
+
+        Index { response, by_name }
+    }
+
+    pub fn response(&self) -> &'a NoogleResponse {
+        self.response
+    }
+
+    /// Finds a doc by title or alias.
+    pub fn lookup(&self, name: &str) -> Option<&'a Doc> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Looks a name up as written, then a bare name relative to `namespace`,
+    /// `lib` and `builtins`, as doc text and `with lib;` code refer to them.
+    pub fn resolve(&self, name: &str, namespace: &str) -> Option<&'a Doc> {
+        if let Some(doc) = self.lookup(name) {
+            return Some(doc);
+        }
+        if name.contains('.') {
+            return None;
+        }
+        [namespace, "lib", "builtins"]
+            .into_iter()
+            .filter(|namespace| !namespace.is_empty())
+            .find_map(|namespace| self.lookup(&format!("{}.{}", namespace, name)))
+    }
+
+    /// Ranks docs against `query`, best first.
+    pub fn search(&self, query: &str, mode: SearchMode, limit: usize) -> Vec<SearchHit<'a>> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let words: Vec<&str> = query.split_whitespace().collect();
+
+        let mut hits: Vec<SearchHit<'a>> = self
+            .response
+            .data
+            .iter()
+            .filter_map(|doc| {
+                let name = match mode {
+                    SearchMode::Text => 0,
+                    _ => doc
+                        .all_names()
+                        .iter()
+                        .map(|name| name_score(&name.to_lowercase(), &query))
+                        .max()
+                        .unwrap_or(0),
+                };
+                let text = match mode {
+                    SearchMode::Fuzzy => 0,
+                    _ => text_score(doc, &words),
+                };
+                let score = name.max(text);
+                (score > 0).then_some(SearchHit { doc, score })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.doc.meta.title.len().cmp(&b.doc.meta.title.len()))
+                .then(a.doc.meta.title.cmp(&b.doc.meta.title))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// Finds docs whose type signature matches `query`, e.g. `string -> [string]`.
+    ///
+    /// Exact signatures rank first, then signatures containing the query, then ones
+    /// mentioning all of its type names in order.
+    pub fn search_by_type(&self, query: &str, limit: usize) -> Vec<SearchHit<'a>> {
+        let wanted = normalize_type(query);
+        let wanted_tokens = type_tokens(query);
+        if wanted_tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let mut hits: Vec<SearchHit<'a>> = self
+            .response
+            .data
+            .iter()
+            .filter_map(|doc| {
+                let sig = doc.meta.signature.as_deref()?;
+                let sig = sig.split_once("::").map_or(sig, |(_, ty)| ty);
+                let normalized = normalize_type(sig);
+                let score = if normalized == wanted {
+                    1000
+                } else if normalized.contains(&wanted) {
+                    600
+                } else if contains_in_order(&type_tokens(sig), &wanted_tokens) {
+                    200
+                } else {
+                    return None;
+                };
+                Some(SearchHit { doc, score })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.doc.meta.title.cmp(&b.doc.meta.title))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// Functions and namespaces one level below `namespace`, following `meta.path` and
+    /// counting alias paths as names.
+    pub fn children(&self, namespace: &str) -> Vec<Child<'a>> {
+        let parent: Vec<&str> = namespace.split('.').filter(|s| !s.is_empty()).collect();
+        let mut children: BTreeMap<&'a str, Option<&'a Doc>> = BTreeMap::new();
+
+        let docs = &self.response.data;
+        let paths = docs.iter().map(|doc| (doc, &doc.meta.path, true));
+        let aliases = docs.iter().flat_map(|doc| {
+            doc.meta
+                .aliases
+                .iter()
+                .flatten()
+                .map(move |alias| (doc, alias, false))
+        });
+
+        for (doc, path, primary) in paths.chain(aliases) {
+            if path.len() <= parent.len() || !path.iter().zip(&parent).all(|(a, b)| a == b) {
+                continue;
+            }
+            let segment = path[parent.len()].as_str();
+            if path.len() > parent.len() + 1 {
+                children.entry(segment).or_insert(None);
+            } else if primary {
+                children.insert(segment, Some(doc));
+            } else {
+                // Paths win over aliases that happen to share a name.
+                children.entry(segment).or_insert(None).get_or_insert(doc);
+            }
+        }
+
+        let prefix = if namespace.is_empty() {
+            String::new()
+        } else {
+            format!("{}.", namespace)
+        };
+        children
+            .into_iter()
+            .map(|(segment, doc)| match doc {
+                Some(doc) => Child::Doc {
+                    name: format!("{}{}", prefix, segment),
+                    doc,
+                },
+                None => Child::Namespace(format!("{}{}", prefix, segment)),
+            })
+            .collect()
+    }
+}
+
+fn name_score(name: &str, query: &str) -> u32 {
+    let last = name.rsplit('.').next().unwrap_or(name);
+
+    if name == query {
+        1000
+    } else if last == query {
+        900
+    } else if last.starts_with(query) {
+        800
+    } else if name.contains(query) {
+        600
+    } else {
+        fuzzy_score(name, query).unwrap_or(0)
+    }
+}
+
+/// Scores `query` as a subsequence of `name`, preferring matches with fewer gaps.
+fn fuzzy_score(name: &str, query: &str) -> Option<u32> {
+    let mut chars = name.char_indices();
+    let mut gaps = 0;
+    let mut last = None;
+
+    for q in query.chars() {
+        let (i, _) = chars.find(|(_, c)| *c == q)?;
+        if let Some(prev) = last
+            && i > prev + 1
+        {
+            gaps += 1;
+        }
+        last = Some(i);
+    }
+
+    Some(400u32.saturating_sub(gaps * 20).max(100))
+}
+
+fn text_score(doc: &Doc, words: &[&str]) -> u32 {
+    let mut haystack = doc.meta.title.to_lowercase();
+    if let Some(sig) = &doc.meta.signature {
+        haystack.push('\n');
+        haystack.push_str(&sig.to_lowercase());
+    }
+    if let Some(text) = doc.content.as_ref().and_then(|c| c.content.as_ref()) {
+        haystack.push('\n');
+        haystack.push_str(&text.to_lowercase());
+    }
+
+    if words.iter().all(|word| haystack.contains(word)) {
+        50
+    } else {
+        0
+    }
+}
+
+fn normalize_type(ty: &str) -> String {
+    ty.chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn type_tokens(ty: &str) -> Vec<String> {
+    ty.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn contains_in_order(haystack: &[String], needles: &[String]) -> bool {
+    let mut rest = haystack.iter();
+    needles.iter().all(|needle| rest.any(|t| t == needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response() -> NoogleResponse {
+        serde_json::from_value(json!({
+            "data": [
+                {
+                    "meta": {
+                        "title": "lib.strings.splitString",
+                        "path": ["lib", "strings", "splitString"],
+                        "aliases": [["lib", "splitString"]],
+                        "is_primop": false,
+                    },
+                },
+                {
+                    // Titled differently from where it lives.
+                    "meta": { "title": "lib.id", "path": ["lib", "trivial", "id"], "is_primop": false },
+                },
+                {
+                    "meta": { "title": "builtins.map", "path": ["builtins", "map"], "is_primop": true },
+                },
+            ],
+            "builtinTypes": {},
+            "upstreamInfo": { "rev": "abc", "lastModified": 0 },
+            "nixInfo": { "rev": "abc", "lastModified": 0 },
+        }))
+        .unwrap()
+    }
+
+    fn names(children: Vec<Child>) -> Vec<String> {
+        children
+            .into_iter()
+            .map(|child| match child {
+                Child::Namespace(name) => format!("{}/", name),
+                Child::Doc { name, .. } => name,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn children_follow_paths_and_aliases() {
+        let response = response();
+        let index = Index::new(&response);
+
+        assert_eq!(
+            names(index.children("lib")),
+            ["lib.splitString", "lib.strings/", "lib.trivial/"]
+        );
+        assert_eq!(names(index.children("lib.trivial")), ["lib.trivial.id"]);
+        assert_eq!(names(index.children("")), ["builtins/", "lib/"]);
+        assert!(index.children("lib.id").is_empty());
+    }
+
+    #[test]
+    fn child_namespaces_lists_one_level() {
+        let counts = response().namespace_counts();
+        assert_eq!(
+            crate::data::child_namespaces(&counts, ""),
+            ["builtins", "lib"]
+        );
+        assert_eq!(
+            crate::data::child_namespaces(&counts, "lib"),
+            ["lib.strings", "lib.trivial"]
+        );
+        assert!(crate::data::child_namespaces(&counts, "lib.strings").is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::io::{BufRead, Write};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// Reads one `Content-Length` framed message; `None` at end of input.
+///
+/// A body that isn't valid JSON is returned as an error so the caller can reply and
+/// keep reading; broken framing is an error of the whole read.
+pub fn read_framed(reader: &mut impl BufRead) -> Result<Option<Incoming>> {
+    let mut length = None;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+
+    let length = length.ok_or_else(|| anyhow!("Message without Content-Length"))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Some(serde_json::from_slice(&body)))
+}
+
+pub fn write_framed(writer: &mut impl Write, message: &Value) -> Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// A message that was read, or why it isn't valid JSON.
+pub type Incoming = std::result::Result<Value, serde_json::Error>;
+
+/// Reads one newline-delimited message, skipping blank lines; `None` at end of input.
+///
+/// A malformed line is returned as an error so the caller can reply and keep reading.
+pub fn read_line(reader: &mut impl BufRead) -> Result<Option<Incoming>> {
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            return Ok(Some(serde_json::from_str(&line)));
+        }
+    }
+}
+
+pub fn write_line(writer: &mut impl Write, message: &Value) -> Result<()> {
+    writeln!(writer, "{}", serde_json::to_string(message)?)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn response(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub fn error(id: &Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// The reply to a message that isn't valid JSON; its id is unknown, so it is `null`.
+pub fn parse_error(err: &serde_json::Error) -> Value {
+    error(&Value::Null, PARSE_ERROR, &format!("Parse error: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_bodies_are_returned_as_parse_errors() {
+        let mut input = "Content-Length: 4\r\n\r\n{bad\
+                         Content-Length: 8\r\n\r\n{\"id\":1}"
+            .as_bytes();
+        let err = read_framed(&mut input).unwrap().unwrap().unwrap_err();
+        assert_eq!(parse_error(&err)["error"]["code"], PARSE_ERROR);
+        assert_eq!(read_framed(&mut input).unwrap().unwrap().unwrap()["id"], 1);
+        assert!(read_framed(&mut input).unwrap().is_none());
+    }
+
+    #[test]
+    fn malformed_lines_are_returned_as_parse_errors() {
+        let mut input = "{not json\n\n{\"id\":1}\n".as_bytes();
+        let err = read_line(&mut input).unwrap().unwrap().unwrap_err();
+        assert_eq!(parse_error(&err)["error"]["code"], PARSE_ERROR);
+        assert_eq!(read_line(&mut input).unwrap().unwrap().unwrap()["id"], 1);
+        assert!(read_line(&mut input).unwrap().is_none());
+    }
+
+    #[test]
+    fn framed_messages_round_trip() {
+        let message = response(&json!(7), json!({ "text": "héllo" }));
+        let mut buffer = Vec::new();
+        write_framed(&mut buffer, &message).unwrap();
+        let message_again = read_framed(&mut buffer.as_slice())
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(message_again, message);
+    }
+
+    #[test]
+    fn framing_without_a_length_is_an_error() {
+        let mut input = "Content-Type: x\r\n\r\n{}".as_bytes();
+        assert!(read_framed(&mut input).is_err());
+    }
+}
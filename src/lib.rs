@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Noogle data for Rust tooling: fetching and caching the Noogle dataset,
+//! looking functions up, and rendering their documentation.
+//!
+//! ```no_run
+//! use noogle_search::{Index, NoogleClient, SearchMode};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let response = NoogleClient::new()?.load()?;
+//! let index = Index::new(&response);
+//!
+//! if let Some(doc) = index.lookup("lib.strings.splitString") {
+//!     let source_urls = noogle_search::urls::SourceUrls::new(&response);
+//!     println!("{}", noogle_search::format::markdown::render_doc(doc, &source_urls));
+//! }
+//!
+//! for hit in index.search("split", SearchMode::All, 5) {
+//!     println!("{}", hit.doc.meta.title);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod cache;
+pub mod config;
+pub mod data;
+pub mod examples;
+pub mod format;
+pub mod index;
+pub mod nixdoc;
+pub mod nixpkgs;
+pub mod related;
+pub mod sources;
+pub mod urls;
+
+pub use cache::NoogleClient;
+pub use data::{Doc, NoogleResponse};
+pub use index::{Child, Index, SearchHit, SearchMode};
@@ -14,14 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-mod cache;
 mod commands;
-mod data;
-mod format;
+mod jsonrpc;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
-use std::io::{self, ErrorKind};
+use clap::{CommandFactory, Parser, Subcommand};
+use noogle_search::config::Config;
+use noogle_search::{Index, NoogleClient, NoogleResponse, cache, config};
+use std::io::{self, ErrorKind, IsTerminal};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "noogle-search")]
@@ -30,8 +31,15 @@ struct Cli {
     #[arg(short = 'f', long = "filter")]
     filter: Option<String>,
 
+    /// When to use colors: auto honours NO_COLOR, CLICOLOR_FORCE and TTY detection
+    #[arg(long, value_enum, global = true, default_value_t = commands::preview::ColorChoice::Auto)]
+    color: commands::preview::ColorChoice,
+
     query: Option<String>,
 
+    #[command(flatten)]
+    primops: commands::print::PrimopFilter,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -41,18 +49,168 @@ enum Commands {
     Print {
         #[arg(long)]
         filter: Option<String>,
+
+        #[command(flatten)]
+        primops: commands::print::PrimopFilter,
     },
     Preview {
         name: String,
+
+        #[arg(long, value_enum, default_value_t = commands::preview::PreviewFormat::Terminal)]
+        format: commands::preview::PreviewFormat,
+
+        /// Show this many lines around the definition from a local nixpkgs, overriding the config file
+        #[arg(long)]
+        source_context: Option<usize>,
+
+        /// Local nixpkgs checkout to read the source from, overriding the config file
+        #[arg(long)]
+        nixpkgs: Option<PathBuf>,
     },
     OpenSource {
         name: String,
+
+        /// Browser command to use instead of $BROWSER or xdg-open; %s marks the URL
+        #[arg(long)]
+        browser: Option<String>,
     },
     OpenNoogle {
         name: String,
+
+        /// Browser command to use instead of $BROWSER or xdg-open; %s marks the URL
+        #[arg(long)]
+        browser: Option<String>,
+    },
+    /// Print a function's source or noogle.dev URL instead of opening it
+    Url {
+        #[arg(value_enum)]
+        kind: commands::url::UrlKind,
+        name: String,
+    },
+    /// Copy a function's name, URL or a usage snippet to the clipboard via OSC 52
+    Copy {
+        #[arg(value_enum)]
+        target: commands::copy::CopyTarget,
+        name: String,
+    },
+    /// Open a function's definition in $VISUAL or $EDITOR at its exact line
+    Edit {
+        name: String,
+
+        /// Local nixpkgs checkout, overriding the config file
+        #[arg(long)]
+        nixpkgs: Option<PathBuf>,
+    },
+    /// List the functions a function's docs mention
+    Refs { name: String },
+    /// List the functions whose docs mention a function
+    Backrefs { name: String },
+    /// List the builtin types used in signatures, optionally matching a query
+    Types { query: Option<String> },
+    /// Extract a function's documented examples with their expected results
+    Examples {
+        name: String,
+
+        #[arg(long, value_enum, default_value_t = commands::examples::ExampleFormat::Json)]
+        format: commands::examples::ExampleFormat,
+    },
+    /// Evaluate documented examples and report results that don't match
+    CheckExamples {
+        /// Only check functions below this namespace, e.g. lib.strings
+        #[arg(long)]
+        ns: Option<String>,
+    },
+    /// Report per namespace how many functions have docs, signatures, examples and positions
+    Coverage {
+        /// Only include functions below this namespace
+        #[arg(long)]
+        ns: Option<String>,
+
+        #[arg(long, value_enum, default_value_t = commands::coverage::CoverageFormat::Markdown)]
+        format: commands::coverage::CoverageFormat,
+    },
+    /// Print the namespace hierarchy with the number of functions in each
+    Tree {
+        namespace: Option<String>,
+
+        /// How many levels below the namespace to show
+        #[arg(long)]
+        depth: Option<usize>,
+    },
+    #[command(hide = true)]
+    Browse {
+        #[command(subcommand)]
+        action: commands::tree::BrowseAction,
+    },
+    /// Render a function or namespace as a roff man page
+    Man {
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        name: Option<String>,
+
+        /// Write pages for every function, namespace and alias
+        #[arg(long, requires = "out_dir")]
+        all: bool,
+
+        /// Directory to write pages into, below a man3/ subdirectory
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+    /// Build the cached data from a local nixpkgs checkout instead of noogle.dev
+    Index {
+        /// Checkout whose lib/ doc comments are indexed
+        #[arg(long, required_unless_present = "noogle", conflicts_with = "noogle")]
+        nixpkgs: Option<PathBuf>,
+
+        /// Go back to the data from noogle.dev
+        #[arg(long)]
+        noogle: bool,
+    },
+    /// Run a language server on stdio with hover, completion and go-to-definition
+    Lsp {
+        /// Local nixpkgs checkout for go-to-definition, overriding the config file
+        #[arg(long)]
+        nixpkgs: Option<PathBuf>,
+    },
+    /// Serve a local HTTP JSON API for lookups, search and rendered previews
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        listen: String,
+        /// Origin allowed to call the API from a browser, e.g. `*` or `http://localhost:3000`
+        #[arg(long, value_name = "ORIGIN")]
+        cors: Option<String>,
+    },
+    /// Run a Model Context Protocol server on stdio for AI assistants
+    Mcp,
+    /// Print a shell completion script, including function names from the cache
+    Completions {
+        #[arg(value_enum)]
+        shell: commands::completions::CompletionShell,
+    },
+    #[command(hide = true)]
+    CompleteValues {
+        #[arg(value_enum)]
+        kind: commands::completions::CompletionValues,
+    },
+    /// Export the cached data for offline documentation browsers
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
     },
 }
 
+#[derive(Subcommand)]
+enum ExportTarget {
+    /// A Dash/Zeal docset, written as <DIR>/Noogle.docset
+    Docset {
+        dir: PathBuf,
+        /// Replace <DIR>/Noogle.docset even if it doesn't look like a docset
+        #[arg(long)]
+        force: bool,
+    },
+    /// A self-contained static HTML site with a client-side search index
+    Html { dir: PathBuf },
+}
+
 fn main() -> Result<()> {
     unsafe {
         libc::signal(libc::SIGPIPE, libc::SIG_DFL);
@@ -60,12 +218,11 @@ fn main() -> Result<()> {
 
     let result = run();
 
-    if let Err(e) = &result {
-        if let Some(io_err) = e.downcast_ref::<io::Error>() {
-            if io_err.kind() == ErrorKind::BrokenPipe {
-                return Ok(());
-            }
-        }
+    if let Err(e) = &result
+        && let Some(io_err) = e.downcast_ref::<io::Error>()
+        && io_err.kind() == ErrorKind::BrokenPipe
+    {
+        return Ok(());
     }
 
     result
@@ -74,8 +231,10 @@ fn main() -> Result<()> {
 fn run() -> Result<()> {
     let cli = Cli::parse();
 
-    // Show GPL disclaimer only once, first time any command is run
-    if cache::should_show_disclaimer()? {
+    let config = config::load_config()?;
+
+    // Show GPL disclaimer only once, first time any command is run interactively
+    if io::stdin().is_terminal() && cache::should_show_disclaimer()? {
         eprintln!("noogle-search  Copyright (C) 2026  argos_nothing");
         eprintln!("This program comes with ABSOLUTELY NO WARRANTY.");
         eprintln!("This is free software, and you are welcome to redistribute it");
@@ -89,26 +248,132 @@ fn run() -> Result<()> {
     }
 
     match cli.command {
-        Some(Commands::Print { filter }) => {
-            let response = cache::load_data()?;
-            commands::print::execute(&response, filter.as_deref());
+        Some(Commands::Print { filter, primops }) => {
+            let response = load_data(&config)?;
+            commands::print::execute(&response, filter.as_deref(), &primops);
+        }
+        Some(Commands::Preview {
+            name,
+            format,
+            source_context,
+            nixpkgs,
+        }) => {
+            let response = load_data(&config)?;
+            let nixpkgs = nixpkgs.or(config.nixpkgs);
+            let source = source_context
+                .or(config.source_context)
+                .filter(|lines| *lines > 0)
+                .map(|lines| commands::preview::SourceContext {
+                    nixpkgs: nixpkgs.as_deref(),
+                    lines,
+                });
+            commands::preview::execute(&response, &name, format, source, cli.color.enabled())?;
+        }
+        Some(Commands::OpenSource { name, browser }) => {
+            let response = load_data(&config)?;
+            commands::open_source::execute(&response, &name, browser.as_deref())?;
+        }
+        Some(Commands::OpenNoogle { name, browser }) => {
+            let response = load_data(&config)?;
+            commands::open_noogle::execute(&response, &name, browser.as_deref())?;
+        }
+        Some(Commands::Url { kind, name }) => {
+            let response = load_data(&config)?;
+            commands::url::execute(&response, kind, &name)?;
         }
-        Some(Commands::Preview { name }) => {
-            let response = cache::load_data()?;
-            commands::preview::execute(&response, &name)?;
+        Some(Commands::Copy { target, name }) => {
+            let response = load_data(&config)?;
+            commands::copy::execute(&response, target, &name)?;
         }
-        Some(Commands::OpenSource { name }) => {
-            let response = cache::load_data()?;
-            commands::open_source::execute(&response, &name)?;
+        Some(Commands::Refs { name }) => {
+            let response = load_data(&config)?;
+            commands::refs::refs(&response, &name)?;
         }
-        Some(Commands::OpenNoogle { name }) => {
-            let response = cache::load_data()?;
-            commands::open_noogle::execute(&response, &name)?;
+        Some(Commands::Backrefs { name }) => {
+            let response = load_data(&config)?;
+            commands::refs::backrefs(&response, &name)?;
+        }
+        Some(Commands::Types { query }) => {
+            let response = load_data(&config)?;
+            commands::types::execute(&response, query.as_deref());
+        }
+        Some(Commands::Examples { name, format }) => {
+            let response = load_data(&config)?;
+            commands::examples::execute(&response, &name, format)?;
+        }
+        Some(Commands::CheckExamples { ns }) => {
+            let response = load_data(&config)?;
+            let command = config.eval_command.unwrap_or_else(|| {
+                commands::check_examples::DEFAULT_EVAL_COMMAND
+                    .iter()
+                    .map(|arg| arg.to_string())
+                    .collect()
+            });
+            commands::check_examples::execute(&response, ns.as_deref(), &command)?;
+        }
+        Some(Commands::Coverage { ns, format }) => {
+            let response = load_data(&config)?;
+            commands::coverage::execute(&response, ns.as_deref(), format)?;
+        }
+        Some(Commands::Tree { namespace, depth }) => {
+            let response = load_data(&config)?;
+            commands::tree::execute(&response, namespace.as_deref(), depth)?;
+        }
+        Some(Commands::Browse { action }) => {
+            commands::tree::browse(action, || load_data(&config))?;
+        }
+        Some(Commands::Man { name, out_dir, .. }) => {
+            let response = load_data(&config)?;
+            commands::man::execute(&response, name.as_deref(), out_dir.as_deref())?;
+        }
+        Some(Commands::Edit { name, nixpkgs }) => {
+            let response = load_data(&config)?;
+            let nixpkgs = nixpkgs.or(config.nixpkgs);
+            commands::edit::execute(&response, &name, nixpkgs.as_deref())?;
+        }
+        Some(Commands::Index { nixpkgs, .. }) => {
+            commands::index::execute(nixpkgs.as_deref())?;
+        }
+        Some(Commands::Lsp { nixpkgs }) => {
+            let response = load_data(&config)?;
+            let nixpkgs = nixpkgs.or(config.nixpkgs);
+            commands::lsp::execute(&response, nixpkgs.as_deref())?;
+        }
+        Some(Commands::Serve { listen, cors }) => {
+            let response = load_data(&config)?;
+            commands::serve::execute(&Index::new(&response), &listen, cors.as_deref())?;
+        }
+        Some(Commands::Mcp) => {
+            let response = load_data(&config)?;
+            commands::mcp::execute(&Index::new(&response))?;
+        }
+        Some(Commands::Completions { shell }) => {
+            commands::completions::execute(Cli::command(), shell)?;
+        }
+        Some(Commands::CompleteValues { kind }) => {
+            let response = load_data(&config)?;
+            commands::completions::values(&response, kind);
+        }
+        Some(Commands::Export { target }) => {
+            let response = load_data(&config)?;
+            match target {
+                ExportTarget::Docset { dir, force } => {
+                    commands::export::docset::execute(&response, &dir, force)?
+                }
+                ExportTarget::Html { dir } => commands::export::site::execute(&response, &dir)?,
+            }
         }
         None => {
-            commands::search::execute(cli.filter, cli.query)?;
+            commands::search::execute(cli.filter, cli.query, &cli.primops)?;
         }
     }
 
     Ok(())
 }
+
+/// The cached data with the configured additional sources merged in.
+fn load_data(config: &Config) -> Result<NoogleResponse> {
+    let mut response = NoogleClient::new()?.load_merged(&config.sources)?;
+    response.source_links = config.source_links.clone();
+    Ok(response)
+}
@@ -0,0 +1,593 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{
+    Content, ContentMeta, Doc, DocMeta, NoogleResponse, Position, PrimopMeta, RepoInfo, SourceInfo,
+};
+use crate::format::markdown::{Block, parse_blocks};
+use crate::urls::SourceLinks;
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Descriptions of common builtins, shipped with the binary for offline indexes.
+const BUNDLED_BUILTINS: &str = include_str!("builtins.json");
+
+/// The types used in builtin signatures, for offline indexes.
+const BUNDLED_TYPES: &str = include_str!("builtin_types.json");
+
+#[derive(Deserialize)]
+struct BundledBuiltin {
+    args: Vec<String>,
+    doc: String,
+}
+
+/// Builds a dataset from the RFC 145 `/** */` doc comments under a nixpkgs checkout's `lib`.
+///
+/// Builtins and builtin types are carried over from `previous`, usually the cached
+/// noogle.dev data; without it only the bundled descriptions of common builtins are used.
+/// Positions are recorded relative to `root`, as source links need them, and `root` is kept
+/// as the dataset's checkout to find the files locally.
+pub fn index_nixpkgs(root: &Path, previous: Option<&NoogleResponse>) -> Result<NoogleResponse> {
+    let lib = root.join("lib");
+    let default_nix = fs::read_to_string(lib.join("default.nix"))
+        .with_context(|| format!("{} is not a nixpkgs checkout", root.display()))?;
+
+    let mut data = Vec::new();
+    for (namespace, file) in lib_files(&lib, &default_nix)? {
+        let relative = format!("lib/{}", file);
+        let source = fs::read_to_string(lib.join(&file))
+            .with_context(|| format!("Failed to read {}", relative))?;
+        let namespace = vec!["lib".to_string(), namespace];
+        data.extend(parse_file(&source, &relative, &namespace));
+    }
+
+    add_aliases(&mut data, &default_nix);
+
+    let previous_builtins: Vec<Doc> = previous
+        .map(|response| {
+            response
+                .data
+                .iter()
+                .filter(|doc| doc.meta.path.first().is_some_and(|p| p == "builtins"))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    if previous_builtins.is_empty() {
+        data.extend(bundled_builtins()?);
+    } else {
+        data.extend(previous_builtins);
+    }
+
+    let builtin_types = match previous {
+        Some(response) if !response.builtin_types.is_empty() => response.builtin_types.clone(),
+        _ => serde_json::from_str(BUNDLED_TYPES).context("Failed to parse bundled types")?,
+    };
+
+    let rev = git_rev(root).unwrap_or_else(|| "local".to_string());
+    let last_modified = Utc::now().timestamp() as u64;
+
+    Ok(NoogleResponse {
+        data,
+        builtin_types,
+        upstream_info: RepoInfo { rev, last_modified },
+        nix_info: previous.map_or_else(
+            || RepoInfo {
+                rev: "bundled".to_string(),
+                last_modified,
+            },
+            |response| response.nix_info.clone(),
+        ),
+        namespace_links: Vec::new(),
+        source_links: SourceLinks::default(),
+        checkout: Some(fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf())),
+    })
+}
+
+/// Namespaces and their files from `name = callLibs ./file.nix;` lines in `lib/default.nix`.
+fn lib_files(lib: &Path, default_nix: &str) -> Result<Vec<(String, String)>> {
+    let mut files = Vec::new();
+
+    for line in default_nix.lines() {
+        let Some((name, rest)) = line.split_once('=') else {
+            continue;
+        };
+        let Some(path) = rest.trim().strip_prefix("callLibs ./") else {
+            continue;
+        };
+        let path = path.trim_end_matches(';').trim();
+        let file = if path.ends_with(".nix") {
+            path.to_string()
+        } else {
+            format!("{}/default.nix", path)
+        };
+        if lib.join(&file).is_file() {
+            files.push((name.trim().to_string(), file));
+        }
+    }
+
+    if files.is_empty() {
+        bail!("No `callLibs` entries found in lib/default.nix");
+    }
+
+    Ok(files)
+}
+
+/// Adds `lib.name` aliases for `inherit (self.namespace) name …;` re-exports.
+fn add_aliases(data: &mut [Doc], default_nix: &str) {
+    let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
+
+    for statement in default_nix.split(';') {
+        let Some(rest) = statement.trim().strip_prefix("inherit (self.") else {
+            continue;
+        };
+        let Some((namespace, names)) = rest.split_once(')') else {
+            continue;
+        };
+        for name in names.split_whitespace() {
+            aliases
+                .entry(format!("lib.{}.{}", namespace.trim(), name))
+                .or_default()
+                .push(name.to_string());
+        }
+    }
+
+    for doc in data {
+        if let Some(names) = aliases.get(&doc.meta.title) {
+            doc.meta.aliases = Some(
+                names
+                    .iter()
+                    .map(|name| vec!["lib".to_string(), name.clone()])
+                    .collect(),
+            );
+        }
+    }
+}
+
+fn bundled_builtins() -> Result<Vec<Doc>> {
+    let builtins: BTreeMap<String, BundledBuiltin> =
+        serde_json::from_str(BUNDLED_BUILTINS).context("Failed to parse bundled builtins")?;
+
+    Ok(builtins
+        .into_iter()
+        .map(|(name, builtin)| {
+            let path = vec!["builtins".to_string(), name.clone()];
+            Doc {
+                meta: DocMeta {
+                    title: path.join("."),
+                    path: path.clone(),
+                    aliases: None,
+                    signature: None,
+                    is_primop: true,
+                    primop_meta: Some(PrimopMeta {
+                        name: Some(name),
+                        arity: Some(builtin.args.len() as u32),
+                        args: builtin.args,
+                        experimental: false,
+                        experimental_feature: None,
+                    }),
+                    is_functor: None,
+                    attr_position: None,
+                    attr_expr: None,
+                    lambda_position: None,
+                    lambda_expr: None,
+                    count_applied: None,
+                    content_meta: None,
+                    option: None,
+                },
+                content: Some(Content {
+                    content: Some(builtin.doc),
+                    source: None,
+                }),
+            }
+        })
+        .collect())
+}
+
+pub(crate) fn git_rev(root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Extracts every attribute preceded by a `/** */` doc comment in one file.
+pub fn parse_file(source: &str, file: &str, namespace: &[String]) -> Vec<Doc> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut docs = Vec::new();
+    let mut i = 0;
+
+    while let Some(start) = find(&chars, i, "/**") {
+        if chars.get(start + 3) == Some(&'*') {
+            i = start + 4;
+            continue;
+        }
+        let Some(end) = find(&chars, start + 3, "*/") else {
+            break;
+        };
+        let comment: String = chars[start + 3..end].iter().collect();
+        i = end + 2;
+
+        let attr_start = skip_whitespace(&chars, i);
+        let Some((attr_path, after_path)) = parse_attr_path(&chars, attr_start) else {
+            continue;
+        };
+        let after_path = skip_whitespace(&chars, after_path);
+        if chars.get(after_path) != Some(&'=') || chars.get(after_path + 1) == Some(&'=') {
+            continue;
+        }
+        let value_start = skip_whitespace(&chars, after_path + 1);
+
+        let position = |index: usize| {
+            let (line, column) = line_column(&chars, index);
+            Position {
+                file: file.to_string(),
+                line,
+                column,
+            }
+        };
+        let attr_position = position(attr_start);
+        let lambda_position = is_lambda(&chars, value_start).then(|| position(value_start));
+
+        let path: Vec<String> = namespace.iter().cloned().chain(attr_path).collect();
+        let content = dedent(&comment);
+
+        docs.push(Doc {
+            meta: DocMeta {
+                title: path.join("."),
+                path: path.clone(),
+                aliases: None,
+                signature: signature(&content),
+                is_primop: false,
+                primop_meta: None,
+                is_functor: None,
+                attr_position: Some(attr_position.clone()),
+                attr_expr: None,
+                lambda_position,
+                lambda_expr: None,
+                count_applied: None,
+                content_meta: Some(ContentMeta {
+                    position: Some(attr_position.clone()),
+                    path: path.clone(),
+                    pos_type: Some("attribute".to_string()),
+                }),
+                option: None,
+            },
+            content: Some(Content {
+                content: Some(content),
+                source: Some(SourceInfo {
+                    position: Some(attr_position),
+                    path,
+                    pos_type: Some("attribute".to_string()),
+                }),
+            }),
+        });
+        i = value_start;
+    }
+
+    docs
+}
+
+/// The code block under the doc comment's `# Type` heading.
+fn signature(content: &str) -> Option<String> {
+    let blocks = parse_blocks(content);
+    let heading = blocks
+        .iter()
+        .position(|b| matches!(b, Block::Heading { text, .. } if text == "Type"))?;
+    blocks[heading + 1..].iter().find_map(|block| match block {
+        Block::Code { code, .. } => Some(code.trim().to_string()),
+        _ => None,
+    })
+}
+
+/// A dotted attribute path such as `foo` or `foo."bar"`, and the index just past it.
+fn parse_attr_path(chars: &[char], mut i: usize) -> Option<(Vec<String>, usize)> {
+    let mut path = Vec::new();
+
+    loop {
+        if chars.get(i) == Some(&'"') {
+            let end = find(chars, i + 1, "\"")?;
+            path.push(chars[i + 1..end].iter().collect());
+            i = end + 1;
+        } else {
+            let start = i;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '\''))
+            {
+                i += 1;
+            }
+            if i == start || !(chars[start].is_alphabetic() || chars[start] == '_') {
+                return None;
+            }
+            path.push(chars[start..i].iter().collect());
+        }
+
+        if chars.get(i) == Some(&'.') {
+            i += 1;
+        } else {
+            return Some((path, i));
+        }
+    }
+}
+
+/// Whether the value starting at `i` is `arg: …` or `{ … }: …`.
+fn is_lambda(chars: &[char], i: usize) -> bool {
+    let after = if chars.get(i) == Some(&'{') {
+        let mut depth = 0;
+        let mut j = i;
+        loop {
+            match chars.get(j) {
+                Some('{') => depth += 1,
+                Some('}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break j + 1;
+                    }
+                }
+                Some(_) => {}
+                None => return false,
+            }
+            j += 1;
+        }
+    } else {
+        match parse_attr_path(chars, i) {
+            Some((path, end)) if path.len() == 1 => end,
+            _ => return false,
+        }
+    };
+
+    let j = skip_whitespace(chars, after);
+    chars.get(j) == Some(&':') || chars.get(j) == Some(&'@')
+}
+
+/// Strips the common indentation and surrounding blank lines of a doc comment.
+fn dedent(comment: &str) -> String {
+    let lines: Vec<&str> = comment.lines().collect();
+    let indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let mut out: Vec<&str> = Vec::new();
+    for (n, line) in lines.iter().enumerate() {
+        if n == 0 {
+            out.push(line.trim());
+        } else {
+            out.push(line.get(indent..).unwrap_or("").trim_end());
+        }
+    }
+
+    out.join("\n").trim_matches('\n').to_string()
+}
+
+fn find(chars: &[char], from: usize, needle: &str) -> Option<usize> {
+    let needle: Vec<char> = needle.chars().collect();
+    (from..chars.len()).find(|&i| chars[i..].starts_with(&needle))
+}
+
+fn skip_whitespace(chars: &[char], mut i: usize) -> usize {
+    while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+        i += 1;
+    }
+    i
+}
+
+fn line_column(chars: &[char], index: usize) -> (u32, u32) {
+    let before = &chars[..index];
+    let line = before.iter().filter(|&&c| c == '\n').count() + 1;
+    let column = index - before.iter().rposition(|&c| c == '\n').map_or(0, |p| p + 1) + 1;
+    (line as u32, column as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn ns() -> Vec<String> {
+        vec!["lib".to_string(), "strings".to_string()]
+    }
+
+    fn titles(docs: &[Doc]) -> Vec<&str> {
+        docs.iter().map(|doc| doc.meta.title.as_str()).collect()
+    }
+
+    #[test]
+    fn skips_triple_star_comments() {
+        let docs = parse_file("{\n  /*** banner */\n  foo = 1;\n}\n", "f.nix", &ns());
+        assert!(docs.is_empty());
+    }
+
+    #[test]
+    fn quoted_attribute_paths() {
+        let docs = parse_file(
+            "{\n  /** Doc. */\n  foo.\"bar-baz\" = 1;\n}\n",
+            "f.nix",
+            &ns(),
+        );
+        assert_eq!(titles(&docs), ["lib.strings.foo.bar-baz"]);
+        assert_eq!(docs[0].meta.path.last().unwrap(), "bar-baz");
+    }
+
+    #[test]
+    fn pattern_lambdas_with_nested_defaults() {
+        let source = "{\n  /** Doc. */\n  f = { a ? {}, b }: a;\n  /** Doc. */\n  g = 1;\n}\n";
+        let docs = parse_file(source, "f.nix", &ns());
+        assert_eq!(titles(&docs), ["lib.strings.f", "lib.strings.g"]);
+        assert!(docs[0].meta.lambda_position.is_some());
+        assert!(docs[1].meta.lambda_position.is_none());
+    }
+
+    #[test]
+    fn simple_and_at_lambdas() {
+        let source =
+            "{\n  /** Doc. */\n  f = x: x;\n  /** Doc. */\n  g = args @ { ... }: args;\n}\n";
+        let docs = parse_file(source, "f.nix", &ns());
+        assert!(docs.iter().all(|doc| doc.meta.lambda_position.is_some()));
+    }
+
+    #[test]
+    fn rejects_comparisons() {
+        let docs = parse_file("/** Doc. */\na == b\n", "f.nix", &ns());
+        assert!(docs.is_empty());
+    }
+
+    #[test]
+    fn records_positions() {
+        let source = "{\n  /**\n    Doc.\n  */\n  foo =\n    x: x;\n}\n";
+        let docs = parse_file(source, "lib/strings.nix", &ns());
+        let attr = docs[0].meta.attr_position.as_ref().unwrap();
+        assert_eq!(
+            (attr.file.as_str(), attr.line, attr.column),
+            ("lib/strings.nix", 5, 3)
+        );
+        let lambda = docs[0].meta.lambda_position.as_ref().unwrap();
+        assert_eq!((lambda.line, lambda.column), (6, 5));
+    }
+
+    #[test]
+    fn dedents_the_comment_and_reads_the_signature() {
+        let source = "{\n  /**\n    Splits.\n\n    # Type\n\n    ```\n    split :: String -> [String]\n    ```\n  */\n  split = s: s;\n}\n";
+        let docs = parse_file(source, "f.nix", &ns());
+        let content = docs[0]
+            .content
+            .as_ref()
+            .unwrap()
+            .content
+            .as_deref()
+            .unwrap();
+        assert!(content.starts_with("Splits.\n\n# Type"));
+        assert_eq!(
+            docs[0].meta.signature.as_deref(),
+            Some("split :: String -> [String]")
+        );
+    }
+
+    #[test]
+    fn inherit_aliases() {
+        let mut docs = parse_file(
+            "{\n  /** Doc. */\n  concat = a: a;\n  /** Doc. */\n  other = 1;\n}\n",
+            "f.nix",
+            &ns(),
+        );
+        add_aliases(
+            &mut docs,
+            "  inherit (self.strings)\n    concat split;\n  inherit (self.lists) map;\n",
+        );
+        assert_eq!(
+            docs[0].meta.aliases,
+            Some(vec![vec!["lib".to_string(), "concat".to_string()]])
+        );
+        assert_eq!(docs[1].meta.aliases, None);
+    }
+
+    #[test]
+    fn lib_files_from_call_libs() {
+        let lib = env::temp_dir().join(format!("noogle-search-nixdoc-{}", std::process::id()));
+        fs::create_dir_all(lib.join("attrsets")).unwrap();
+        fs::write(lib.join("strings.nix"), "{ }").unwrap();
+        fs::write(lib.join("attrsets/default.nix"), "{ }").unwrap();
+
+        let default_nix = "  strings = callLibs ./strings.nix;\n  attrsets = callLibs ./attrsets;\n  missing = callLibs ./missing.nix;\n  other = 1;\n";
+        let files = lib_files(&lib, default_nix).unwrap();
+        fs::remove_dir_all(&lib).unwrap();
+
+        assert_eq!(
+            files,
+            [
+                ("strings".to_string(), "strings.nix".to_string()),
+                ("attrsets".to_string(), "attrsets/default.nix".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn index_keeps_builtins_and_types_from_the_previous_data() {
+        let root = env::temp_dir().join(format!("noogle-search-index-{}", std::process::id()));
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::write(
+            root.join("lib/default.nix"),
+            "  strings = callLibs ./strings.nix;\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("lib/strings.nix"),
+            "{\n  /** Doc. */\n  id = x: x;\n}\n",
+        )
+        .unwrap();
+
+        let offline = index_nixpkgs(&root, None).unwrap();
+        let previous = NoogleResponse {
+            data: vec![Doc {
+                content: None,
+                ..offline.data[1].clone()
+            }],
+            builtin_types: BTreeMap::new(),
+            ..index_nixpkgs(&root, None).unwrap()
+        };
+        let carried = index_nixpkgs(&root, Some(&previous)).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(offline.data[0].meta.title, "lib.strings.id");
+        assert!(offline.data.len() > 50);
+        assert!(offline.builtin_types.contains_key("AttrSet"));
+
+        assert_eq!(carried.data.len(), 2);
+        assert!(carried.data[1].content.is_none());
+        assert!(carried.builtin_types.contains_key("String"));
+    }
+
+    #[test]
+    fn indexed_positions_resolve_to_the_checkout() {
+        let root = env::temp_dir().join(format!("noogle-search-checkout-{}", std::process::id()));
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::write(
+            root.join("lib/default.nix"),
+            "  strings = callLibs ./strings.nix;\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("lib/strings.nix"),
+            "{\n  /** Doc. */\n  id = x: x;\n}\n",
+        )
+        .unwrap();
+
+        let response = index_nixpkgs(&root, None).unwrap();
+        let position = response.data[0].meta.attr_position.as_ref().unwrap();
+        let checkout = crate::nixpkgs::checkout(&response, Some(Path::new("/elsewhere")));
+        let resolved = crate::nixpkgs::resolve(position, checkout);
+        let expected = fs::canonicalize(root.join("lib/strings.nix")).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(position.file, "lib/strings.nix");
+        assert_eq!(resolved, Some(expected));
+    }
+}
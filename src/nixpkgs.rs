@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{NoogleResponse, Position};
+use crate::urls;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Maps a source position to a file on this machine.
+///
+/// Tries `checkout`, then the `nixpkgs` entry of `NIX_PATH`, then the store path itself.
+pub fn resolve(position: &Position, checkout: Option<&Path>) -> Option<PathBuf> {
+    let relative = urls::relative_path(&position.file);
+
+    checkout
+        .map(Path::to_path_buf)
+        .into_iter()
+        .chain(nix_path_nixpkgs())
+        .map(|root| root.join(relative))
+        .chain(std::iter::once(PathBuf::from(&position.file)))
+        .find(|path| path.is_file())
+}
+
+/// The checkout to resolve `response`'s positions in: the one an offline index was built
+/// from, otherwise `configured`.
+pub fn checkout<'a>(
+    response: &'a NoogleResponse,
+    configured: Option<&'a Path>,
+) -> Option<&'a Path> {
+    response.checkout.as_deref().or(configured)
+}
+
+/// The `nixpkgs=<path>` entry of `NIX_PATH`, or a `nixpkgs` directory below a plain entry.
+fn nix_path_nixpkgs() -> Option<PathBuf> {
+    let nix_path = env::var("NIX_PATH").ok()?;
+
+    nix_path
+        .split(':')
+        .find_map(|entry| match entry.split_once('=') {
+            Some(("nixpkgs", path)) => Some(PathBuf::from(path)),
+            Some(_) => None,
+            None => Some(Path::new(entry).join("nixpkgs")).filter(|p| p.is_dir()),
+        })
+}
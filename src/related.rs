@@ -0,0 +1,321 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Doc, NoogleResponse};
+use crate::index::Index;
+use std::collections::HashSet;
+
+const MAX_SIBLINGS: usize = 8;
+const MAX_SIMILAR: usize = 5;
+const MIN_SIMILARITY: f64 = 0.5;
+
+/// Functions named in backticks in `doc`'s text, in order of first mention.
+pub fn references<'a>(doc: &Doc, index: &Index<'a>) -> Vec<&'a Doc> {
+    let Some(text) = doc.content.as_ref().and_then(|c| c.content.as_deref()) else {
+        return Vec::new();
+    };
+
+    let mut found: Vec<&'a Doc> = Vec::new();
+    for span in text.split('`').skip(1).step_by(2) {
+        let span = span.trim();
+        if !is_name(span) {
+            continue;
+        }
+        if let Some(target) = index.resolve(span, &doc.namespace())
+            && target.meta.title != doc.meta.title
+            && !found.iter().any(|d| d.meta.title == target.meta.title)
+        {
+            found.push(target);
+        }
+    }
+    found
+}
+
+/// Whether `text` is a plain, possibly dotted, identifier.
+pub fn is_name(text: &str) -> bool {
+    !text.is_empty()
+        && text.split('.').all(|part| {
+            part.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && part
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '\''))
+        })
+}
+
+/// Functions related to one doc, for the preview's "See also" section.
+#[derive(Debug, Default)]
+pub struct SeeAlso {
+    /// Other docs for the same function under another name.
+    pub aliases: Vec<String>,
+    /// Functions referenced in the doc text.
+    pub mentions: Vec<String>,
+    /// Neighbours in the same namespace.
+    pub siblings: Vec<String>,
+    /// Functions with a similar type signature.
+    pub similar: Vec<String>,
+}
+
+impl SeeAlso {
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+            && self.mentions.is_empty()
+            && self.siblings.is_empty()
+            && self.similar.is_empty()
+    }
+}
+
+pub fn see_also(index: &Index, doc: &Doc) -> SeeAlso {
+    let response = index.response();
+    let own_names: HashSet<String> = doc.all_names().into_iter().collect();
+    let title = &doc.meta.title;
+
+    let aliases: Vec<String> = response
+        .data
+        .iter()
+        .filter(|other| &other.meta.title != title)
+        .filter(|other| other.all_names().iter().any(|n| own_names.contains(n)))
+        .map(|other| other.meta.title.clone())
+        .collect();
+
+    let mentions: Vec<String> = references(doc, index)
+        .into_iter()
+        .map(|d| d.meta.title.clone())
+        .filter(|t| !aliases.contains(t))
+        .collect();
+    let listed: Vec<String> = aliases.iter().chain(&mentions).cloned().collect();
+    let similar = similar_signatures(response, doc, &listed);
+
+    let namespace = response.docs_in_namespace(&doc.namespace());
+    let position = namespace
+        .iter()
+        .position(|d| &d.meta.title == title)
+        .unwrap_or(0);
+    let start = position
+        .saturating_sub(MAX_SIBLINGS / 2)
+        .min(namespace.len().saturating_sub(MAX_SIBLINGS + 1));
+    let siblings = namespace
+        .iter()
+        .skip(start)
+        .filter(|d| &d.meta.title != title)
+        .take(MAX_SIBLINGS)
+        .filter(|d| !mentions.contains(&d.meta.title) && !similar.contains(&d.meta.title))
+        .map(|d| d.meta.title.clone())
+        .collect();
+
+    SeeAlso {
+        aliases,
+        mentions,
+        siblings,
+        similar,
+    }
+}
+
+/// Functions whose signature types share most of their tokens with `doc`'s.
+fn similar_signatures(response: &NoogleResponse, doc: &Doc, exclude: &[String]) -> Vec<String> {
+    let Some(tokens) = doc.meta.signature.as_deref().map(type_tokens) else {
+        return Vec::new();
+    };
+
+    let mut scored: Vec<(f64, &str)> = response
+        .data
+        .iter()
+        .filter(|other| other.meta.title != doc.meta.title)
+        .filter(|other| !exclude.contains(&other.meta.title))
+        .filter_map(|other| {
+            let other_tokens = type_tokens(other.meta.signature.as_deref()?);
+            let shared = tokens.intersection(&other_tokens).count() as f64;
+            let all = tokens.union(&other_tokens).count() as f64;
+            let score = if all == 0.0 { 0.0 } else { shared / all };
+            (score >= MIN_SIMILARITY).then_some((score, other.meta.title.as_str()))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(b.1)));
+    scored
+        .into_iter()
+        .take(MAX_SIMILAR)
+        .map(|(_, title)| title.to_string())
+        .collect()
+}
+
+/// The shape of a signature's type: its words and arrows, ignoring the function name.
+fn type_tokens(signature: &str) -> HashSet<String> {
+    let ty = signature.split_once("::").map_or(signature, |(_, ty)| ty);
+    let mut tokens: HashSet<String> = ty
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    tokens.insert(format!("arity:{}", ty.matches("->").count()));
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn doc(title: &str, signature: Option<&str>, content: &str, aliases: Value) -> Value {
+        json!({
+            "meta": {
+                "title": title,
+                "path": title.split('.').collect::<Vec<_>>(),
+                "aliases": aliases,
+                "signature": signature,
+                "is_primop": false,
+            },
+            "content": { "content": content },
+        })
+    }
+
+    fn response() -> NoogleResponse {
+        let mut data = vec![
+            doc(
+                "lib.attrsets.mapAttrs",
+                Some("mapAttrs :: (String -> Any -> Any) -> AttrSet -> AttrSet"),
+                "Like `builtins.mapAttrs`, see `filterAttrs` and `mapAttrs`, or `{ a = 1; }`.",
+                json!([["builtins", "mapAttrs"]]),
+            ),
+            doc(
+                "lib.attrsets.filterAttrs",
+                Some("filterAttrs :: (String -> Any -> Bool) -> AttrSet -> AttrSet"),
+                "Keep some attributes, unlike `lib.attrsets.filterAttrs` or `nope`.",
+                Value::Null,
+            ),
+            doc(
+                "builtins.mapAttrs",
+                Some("mapAttrs :: (String -> Any -> Any) -> AttrSet -> AttrSet"),
+                "",
+                Value::Null,
+            ),
+            doc(
+                "lib.attrsets.mapAttrsRecursive",
+                Some("mapAttrsRecursive :: ([String] -> Any -> Any) -> AttrSet -> AttrSet"),
+                "",
+                Value::Null,
+            ),
+            doc(
+                "lib.lists.length",
+                Some("length :: [a] -> Int"),
+                "",
+                Value::Null,
+            ),
+        ];
+        for i in 0..12 {
+            data.push(doc(
+                &format!("lib.strings.f{:02}", i),
+                None,
+                "",
+                Value::Null,
+            ));
+        }
+
+        serde_json::from_value(json!({
+            "data": data,
+            "builtinTypes": {},
+            "upstreamInfo": { "rev": "abc", "lastModified": 0 },
+            "nixInfo": { "rev": "abc", "lastModified": 0 },
+        }))
+        .unwrap()
+    }
+
+    fn titles(docs: Vec<&Doc>) -> Vec<&str> {
+        docs.into_iter().map(|d| d.meta.title.as_str()).collect()
+    }
+
+    fn see_also_of(index: &Index, title: &str) -> SeeAlso {
+        see_also(index, index.lookup(title).unwrap())
+    }
+
+    #[test]
+    fn references_resolve_backticked_names_once() {
+        let response = response();
+        let index = Index::new(&response);
+
+        // Bare names resolve in the doc's namespace; the doc itself and code are skipped.
+        assert_eq!(
+            titles(references(&response.data[0], &index)),
+            ["builtins.mapAttrs", "lib.attrsets.filterAttrs"]
+        );
+        assert!(references(&response.data[1], &index).is_empty());
+    }
+
+    #[test]
+    fn names_are_dotted_identifiers() {
+        assert!(is_name("lib.strings.splitString"));
+        assert!(is_name("foldl'"));
+        assert!(is_name("_module.args"));
+        assert!(!is_name(""));
+        assert!(!is_name("a..b"));
+        assert!(!is_name("1 + 2"));
+        assert!(!is_name("lib.1st"));
+    }
+
+    #[test]
+    fn aliases_point_both_ways() {
+        let response = response();
+        let index = Index::new(&response);
+
+        let see_also = see_also(&index, &response.data[0]);
+        assert_eq!(see_also.aliases, ["builtins.mapAttrs"]);
+        // Already listed as an alias, so not repeated as a mention.
+        assert_eq!(see_also.mentions, ["lib.attrsets.filterAttrs"]);
+
+        let see_also = see_also_of(&index, "builtins.mapAttrs");
+        assert_eq!(see_also.aliases, ["lib.attrsets.mapAttrs"]);
+    }
+
+    #[test]
+    fn similar_signatures_share_most_type_words() {
+        let response = response();
+        let index = Index::new(&response);
+
+        let see_also = see_also_of(&index, "lib.attrsets.filterAttrs");
+        assert_eq!(
+            see_also.similar,
+            [
+                "builtins.mapAttrs",
+                "lib.attrsets.mapAttrs",
+                "lib.attrsets.mapAttrsRecursive"
+            ]
+        );
+        assert!(!see_also.similar.iter().any(|t| t == "lib.lists.length"));
+        // Similar functions aren't listed again as siblings.
+        assert!(see_also.siblings.is_empty());
+    }
+
+    #[test]
+    fn siblings_are_a_window_around_the_doc() {
+        let response = response();
+        let index = Index::new(&response);
+        let names = |range: std::ops::Range<usize>| -> Vec<String> {
+            range.map(|i| format!("lib.strings.f{:02}", i)).collect()
+        };
+
+        let first = see_also_of(&index, "lib.strings.f00");
+        assert_eq!(first.siblings, names(1..9));
+
+        let middle = see_also_of(&index, "lib.strings.f06");
+        let expected: Vec<String> = names(2..11)
+            .into_iter()
+            .filter(|t| !t.ends_with("f06"))
+            .collect();
+        assert_eq!(middle.siblings, expected);
+
+        let last = see_also_of(&index, "lib.strings.f11");
+        assert_eq!(last.siblings, names(3..11));
+    }
+}
@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Content, Declaration, Doc, DocMeta, NoogleResponse, OptionMeta, OptionValue};
+use crate::nixdoc;
+use crate::urls::{NamespaceLinks, SourceLinks};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What a source's `path` contains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    /// A directory of `.nix` files with RFC 145 doc comments, or a Noogle-schema JSON file.
+    #[default]
+    Docs,
+    /// An `options.json` from a NixOS or Home Manager manual build.
+    Options,
+}
+
+/// An additional set of docs merged in under `namespace`, from the `sources` config entry.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DocSource {
+    pub namespace: String,
+    pub path: PathBuf,
+    #[serde(default)]
+    pub kind: SourceKind,
+    /// Where source links for this source point; without it they are omitted.
+    #[serde(default)]
+    pub links: Option<SourceLinks>,
+    /// Revision used in source links; defaults to the directory's git `HEAD`.
+    #[serde(default)]
+    pub rev: Option<String>,
+}
+
+/// Adds the docs of every source to `response`, along with their source links.
+pub fn merge(response: &mut NoogleResponse, sources: &[DocSource]) -> Result<()> {
+    for source in sources {
+        let docs = match source.kind {
+            SourceKind::Options => load_options(&source.path, &source.namespace)?,
+            SourceKind::Docs if source.path.is_dir() => {
+                index_directory(&source.path, &source.namespace)?
+            }
+            SourceKind::Docs => load_json(&source.path, &source.namespace)?,
+        };
+
+        let rev = source
+            .rev
+            .clone()
+            .or_else(|| {
+                source
+                    .path
+                    .is_dir()
+                    .then(|| nixdoc::git_rev(&source.path))
+                    .flatten()
+            })
+            .unwrap_or_else(|| "HEAD".to_string());
+
+        response
+            .namespace_links
+            .retain(|links| links.namespace != source.namespace);
+        response.namespace_links.push(NamespaceLinks {
+            namespace: source.namespace.clone(),
+            root: source
+                .path
+                .is_dir()
+                .then(|| source.path.display().to_string()),
+            links: source.links.clone(),
+            rev,
+        });
+
+        response.data.extend(docs);
+    }
+
+    Ok(())
+}
+
+/// Parses every `.nix` file below `root`; `dir/file.nix` becomes `namespace.dir.file`.
+fn index_directory(root: &Path, namespace: &str) -> Result<Vec<Doc>> {
+    let mut files = Vec::new();
+    collect_nix_files(root, &mut files)?;
+    files.sort();
+
+    let mut docs = Vec::new();
+    for file in files {
+        let source = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+
+        let relative = file.strip_prefix(root).unwrap_or(&file).with_extension("");
+        let mut path = vec![namespace.to_string()];
+        path.extend(
+            relative
+                .iter()
+                .map(|segment| segment.to_string_lossy().into_owned())
+                .filter(|segment| segment != "default"),
+        );
+
+        docs.extend(nixdoc::parse_file(
+            &source,
+            &file.display().to_string(),
+            &path,
+        ));
+    }
+
+    Ok(docs)
+}
+
+fn collect_nix_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_nix_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "nix") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Reads a Noogle-schema file, prefixing paths that don't already start with `namespace`.
+fn load_json(path: &Path, namespace: &str) -> Result<Vec<Doc>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let response: NoogleResponse = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let mut docs = response.data;
+    for doc in &mut docs {
+        prefix(&mut doc.meta.path, namespace);
+        doc.meta.title = doc.meta.path.join(".");
+        for alias in doc.meta.aliases.iter_mut().flatten() {
+            prefix(alias, namespace);
+        }
+    }
+
+    Ok(docs)
+}
+
+fn prefix(path: &mut Vec<String>, namespace: &str) {
+    if path.first().map(String::as_str) != Some(namespace) {
+        path.insert(0, namespace.to_string());
+    }
+}
+
+#[derive(Deserialize)]
+struct RawOption {
+    #[serde(default)]
+    loc: Vec<String>,
+    #[serde(rename = "type")]
+    option_type: Option<String>,
+    description: Option<Value>,
+    default: Option<Value>,
+    example: Option<Value>,
+    #[serde(default)]
+    declarations: Vec<Value>,
+    #[serde(default, rename = "readOnly")]
+    read_only: bool,
+}
+
+/// Reads an `options.json`, turning each option into a doc below `namespace`.
+fn load_options(path: &Path, namespace: &str) -> Result<Vec<Doc>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let options: BTreeMap<String, RawOption> = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    Ok(options
+        .into_iter()
+        .map(|(name, option)| {
+            let loc = if option.loc.is_empty() {
+                name.split('.').map(str::to_string).collect()
+            } else {
+                option.loc
+            };
+            let path: Vec<String> = std::iter::once(namespace.to_string()).chain(loc).collect();
+
+            Doc {
+                meta: DocMeta {
+                    title: path.join("."),
+                    path,
+                    aliases: None,
+                    signature: None,
+                    is_primop: false,
+                    primop_meta: None,
+                    is_functor: None,
+                    attr_position: None,
+                    attr_expr: None,
+                    lambda_position: None,
+                    lambda_expr: None,
+                    count_applied: None,
+                    content_meta: None,
+                    option: Some(OptionMeta {
+                        option_type: option.option_type,
+                        default: option.default.as_ref().map(option_value),
+                        example: option.example.as_ref().map(option_value),
+                        declarations: option.declarations.iter().filter_map(declaration).collect(),
+                        read_only: option.read_only,
+                    }),
+                },
+                content: Some(Content {
+                    content: option.description.as_ref().map(literal),
+                    source: None,
+                }),
+            }
+        })
+        .collect())
+}
+
+/// The text of a `literalExpression`/`literalMD`/`mdDoc` value, or a plain value as JSON.
+fn literal(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Object(object) if object.contains_key("_type") => object
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// A default or example: Markdown for a `literalMD`, otherwise Nix code.
+fn option_value(value: &Value) -> OptionValue {
+    match value["_type"].as_str() {
+        Some("literalMD") => OptionValue::Markdown(literal(value)),
+        _ => OptionValue::Nix(nix_literal(value)),
+    }
+}
+
+/// A default or example: the text of a `literalExpression`, or a plain value written as Nix.
+fn nix_literal(value: &Value) -> String {
+    match value {
+        Value::Object(object) if object.contains_key("_type") => literal(value),
+        other => nix_value(other),
+    }
+}
+
+fn nix_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => nix_string(text),
+        Value::Array(items) if items.is_empty() => "[ ]".to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| match item {
+                    // A negative literal would be read as a subtraction.
+                    Value::Number(n) if n.to_string().starts_with('-') => format!("({})", n),
+                    other => nix_value(other),
+                })
+                .collect();
+            format!("[ {} ]", items.join(" "))
+        }
+        Value::Object(object) if object.is_empty() => "{ }".to_string(),
+        Value::Object(object) => {
+            let attrs: Vec<String> = object
+                .iter()
+                .map(|(name, value)| format!("{} = {};", nix_attr(name), nix_value(value)))
+                .collect();
+            format!("{{ {} }}", attrs.join(" "))
+        }
+        other => other.to_string(),
+    }
+}
+
+fn nix_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+/// An attribute name, quoted unless it is a plain identifier.
+fn nix_attr(name: &str) -> String {
+    let mut chars = name.chars();
+    let identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '\'' | '-'));
+    if identifier {
+        name.to_string()
+    } else {
+        nix_string(name)
+    }
+}
+
+/// A declaration given as a path (NixOS) or as `{ name, url }` (Home Manager).
+fn declaration(value: &Value) -> Option<Declaration> {
+    match value {
+        Value::String(name) => Some(Declaration {
+            name: name.clone(),
+            url: None,
+        }),
+        Value::Object(object) => Some(Declaration {
+            name: object.get("name")?.as_str()?.to_string(),
+            url: object
+                .get("url")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn plain_values_are_written_as_nix() {
+        assert_eq!(nix_literal(&json!("foo")), "\"foo\"");
+        assert_eq!(
+            nix_literal(&json!("a \"b\" ${c}\\\n")),
+            r#""a \"b\" \${c}\\\n""#
+        );
+        assert_eq!(nix_literal(&json!([1, -2, "x"])), r#"[ 1 (-2) "x" ]"#);
+        assert_eq!(
+            nix_literal(&json!({ "enable": true, "foo.bar": null, "list": [] })),
+            r#"{ enable = true; "foo.bar" = null; list = [ ]; }"#
+        );
+        assert_eq!(nix_literal(&json!({})), "{ }");
+    }
+
+    #[test]
+    fn literal_expressions_keep_their_text() {
+        let value = json!({ "_type": "literalExpression", "text": "pkgs.hello" });
+        assert_eq!(nix_literal(&value), "pkgs.hello");
+        assert_eq!(
+            option_value(&value),
+            OptionValue::Nix("pkgs.hello".to_string())
+        );
+        let value = json!({ "_type": "literalMD", "text": "The *host* name." });
+        assert_eq!(
+            option_value(&value),
+            OptionValue::Markdown("The *host* name.".to_string())
+        );
+        assert_eq!(literal(&json!("Some *markdown*.")), "Some *markdown*.");
+    }
+}
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+// Copyright (C) 2026 argos_nothing <argosnothing@gmail.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Doc, NoogleResponse, Position};
+use serde::{Deserialize, Serialize};
+
+/// The code hosting software serving a repository, which decides the URL layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Forge {
+    #[default]
+    Github,
+    Gitlab,
+    Gitea,
+    Forgejo,
+    Sourcehut,
+}
+
+impl Forge {
+    fn template(self, range: bool) -> &'static str {
+        match (self, range) {
+            (Forge::Github, false) => "{repo}/blob/{rev}/{path}#L{line}",
+            (Forge::Github, true) => "{repo}/blob/{rev}/{path}#L{line}-L{end_line}",
+            (Forge::Gitlab, false) => "{repo}/-/blob/{rev}/{path}#L{line}",
+            (Forge::Gitlab, true) => "{repo}/-/blob/{rev}/{path}#L{line}-{end_line}",
+            (Forge::Gitea | Forge::Forgejo, false) => "{repo}/src/commit/{rev}/{path}#L{line}",
+            (Forge::Gitea | Forge::Forgejo, true) => {
+                "{repo}/src/commit/{rev}/{path}#L{line}-L{end_line}"
+            }
+            (Forge::Sourcehut, false) => "{repo}/tree/{rev}/item/{path}#L{line}",
+            (Forge::Sourcehut, true) => "{repo}/tree/{rev}/item/{path}#L{line}-{end_line}",
+        }
+    }
+}
+
+/// How source links are built, from the `source_links` entry of the config file.
+///
+/// `template` overrides the forge's layout and may use `{repo}`, `{rev}`, `{path}`,
+/// `{line}` and `{end_line}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SourceLinks {
+    pub forge: Forge,
+    pub repo: String,
+    pub template: Option<String>,
+}
+
+impl Default for SourceLinks {
+    fn default() -> Self {
+        SourceLinks {
+            forge: Forge::Github,
+            repo: "https://github.com/NixOS/nixpkgs".to_string(),
+            template: None,
+        }
+    }
+}
+
+impl SourceLinks {
+    pub fn url(&self, rev: &str, path: &str, line: u32, end_line: u32) -> String {
+        let template = self
+            .template
+            .as_deref()
+            .unwrap_or_else(|| self.forge.template(end_line > line));
+
+        template
+            .replace("{repo}", self.repo.trim_end_matches('/'))
+            .replace("{rev}", rev)
+            .replace("{path}", path)
+            .replace("{line}", &line.to_string())
+            .replace("{end_line}", &end_line.to_string())
+    }
+}
+
+/// Source links for docs of an additional source, overriding the nixpkgs ones.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NamespaceLinks {
+    /// First attribute path segment of the source's docs.
+    pub namespace: String,
+    /// Directory the source's positions are below; stripped to get repository paths.
+    pub root: Option<String>,
+    /// `None` when the source has no repository to link to.
+    pub links: Option<SourceLinks>,
+    pub rev: String,
+}
+
+pub fn noogle_url(doc: &Doc) -> String {
+    format!("https://noogle.dev/f/{}", doc.meta.path.join("/"))
+}
+
+/// The position used for source links: the lambda if known, otherwise the attribute.
+pub fn source_position(doc: &Doc) -> Option<&Position> {
+    doc.meta
+        .lambda_position
+        .as_ref()
+        .or(doc.meta.attr_position.as_ref())
+}
+
+/// The lines highlighted by source links: from the attribute to the lambda when both
+/// are known and in the same file, otherwise the single line of [`source_position`].
+///
+/// The data only has start positions, so this never covers the function body: the range
+/// ends at the line where the lambda starts, not where it ends.
+pub fn source_lines(doc: &Doc) -> Option<(u32, u32)> {
+    let position = source_position(doc)?;
+
+    match (&doc.meta.attr_position, &doc.meta.lambda_position) {
+        (Some(attr), Some(lambda)) if attr.file == lambda.file => {
+            Some((attr.line.min(lambda.line), attr.line.max(lambda.line)))
+        }
+        _ => Some((position.line, position.line)),
+    }
+}
+
+/// Builds the source links for the docs of one dataset.
+#[derive(Debug, Clone, Copy)]
+pub struct SourceUrls<'a> {
+    links: &'a SourceLinks,
+    rev: &'a str,
+    namespaces: &'a [NamespaceLinks],
+}
+
+impl<'a> SourceUrls<'a> {
+    /// Links into nixpkgs at the dataset's revision, and into the sources merged into it.
+    pub fn new(response: &'a NoogleResponse) -> Self {
+        SourceUrls {
+            links: &response.source_links,
+            rev: &response.upstream_info.rev,
+            namespaces: &response.namespace_links,
+        }
+    }
+
+    /// The noogle.dev page of `doc`, unless it comes from a merged source noogle.dev
+    /// doesn't know about.
+    pub fn noogle_url(&self, doc: &Doc) -> Option<String> {
+        let merged = self
+            .namespaces
+            .iter()
+            .any(|n| doc.meta.path.first() == Some(&n.namespace));
+        (!merged && doc.meta.option.is_none()).then(|| noogle_url(doc))
+    }
+
+    pub fn url(&self, doc: &Doc) -> Option<String> {
+        let position = source_position(doc)?;
+        let (line, end_line) = source_lines(doc)?;
+
+        if let Some(namespace) = self
+            .namespaces
+            .iter()
+            .find(|n| doc.meta.path.first() == Some(&n.namespace))
+        {
+            let path = match &namespace.root {
+                Some(root) => position
+                    .file
+                    .strip_prefix(root.as_str())
+                    .map_or(position.file.as_str(), |p| p.trim_start_matches('/')),
+                None => relative_path(&position.file),
+            };
+            return Some(
+                namespace
+                    .links
+                    .as_ref()?
+                    .url(&namespace.rev, path, line, end_line),
+            );
+        }
+
+        Some(
+            self.links
+                .url(self.rev, relative_path(&position.file), line, end_line),
+        )
+    }
+}
+
+/// The path of a store file relative to the root of the repository it was copied from.
+pub fn relative_path(file: &str) -> &str {
+    if let Some(entry) = file.strip_prefix("/nix/store/")
+        && let Some((_, path)) = entry.split_once('/')
+    {
+        return path;
+    }
+    file.split("-source/").nth(1).unwrap_or(file)
+}